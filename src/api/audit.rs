@@ -0,0 +1,322 @@
+use crate::api::http::management::api::ManagementApi;
+use crate::api::http::management::model::inventory::Inventory;
+use crate::api::http::management::model::registration::Registration;
+use crate::api::identifier::IcebergLocation;
+use crate::api::resolve::ResolvedTable;
+use crate::api::store::catalog::dici::DiciCatalog;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use futures::try_join;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+// Tables are checked this many at a time so a large warehouse doesn't flood the catalog.
+const TABLE_CONCURRENCY: usize = 16;
+type TableKey = (IcebergLocation, String);
+fn table_key(inventory: &Inventory) -> TableKey {
+    (
+        inventory.id.iceberg_location.iceberg_location.clone(),
+        inventory.id.schema_table.schema_table.clone(),
+    )
+}
+// An inventory last synced before the current snapshot of its table was committed.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StaleInventory {
+    pub inventory: Inventory,
+    pub snapshot_id: i64,
+    pub snapshot_committed_at: DateTime<Utc>,
+}
+// A table the catalog failed to answer for, so its inventories could not be checked.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UncheckedTable {
+    pub iceberg_location: IcebergLocation,
+    pub schema_table: String,
+    pub reason: String,
+}
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TableSync {
+    pub iceberg_location: IcebergLocation,
+    pub schema_table: String,
+    pub inventories: Vec<Inventory>,
+    pub error: Option<String>,
+}
+// Cross-checks every inventory and registration of dici management against the warehouse catalog.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditReport {
+    pub checked_at: DateTime<Utc>,
+    pub inventories: usize,
+    pub registrations: usize,
+    pub tables: usize,
+    // Inventories pointing at tables that don't exist.
+    pub missing_tables: Vec<Inventory>,
+    // Registrations no inventory points at.
+    pub orphan_registrations: Vec<Registration>,
+    pub stale_inventories: Vec<StaleInventory>,
+    pub unchecked_tables: Vec<UncheckedTable>,
+    // Only filled when stale inventories are synced.
+    pub synced: Vec<TableSync>,
+}
+impl AuditReport {
+    // With `sync`, the tables of stale inventories are synced through sync_table.
+    pub async fn run(
+        management_client: &dyn ManagementApi,
+        dici_catalog: &DiciCatalog,
+        sync: bool,
+    ) -> Result<Self> {
+        let checked_at = Utc::now();
+        let (inventories, registrations) = try_join!(
+            management_client.fetch_inventories(),
+            management_client.fetch_registrations()
+        )?;
+        let keys: BTreeSet<TableKey> = inventories.iter().map(table_key).collect();
+        let checked: Vec<(TableKey, Result<ResolvedTable>)> = stream::iter(keys.iter().cloned())
+            .map(|(iceberg_location, schema_table)| async move {
+                let key = (iceberg_location.clone(), schema_table.clone());
+                let table =
+                    ResolvedTable::resolve(dici_catalog, iceberg_location, schema_table).await;
+                (key, table)
+            })
+            .buffer_unordered(TABLE_CONCURRENCY)
+            .collect()
+            .await;
+        let mut tables = BTreeMap::new();
+        let mut unchecked_tables = vec![];
+        for ((iceberg_location, schema_table), table) in checked {
+            match table {
+                Ok(table) => {
+                    tables.insert((iceberg_location, schema_table), table);
+                }
+                Err(e) => unchecked_tables.push(UncheckedTable {
+                    iceberg_location,
+                    schema_table,
+                    reason: format!("{:#}", e),
+                }),
+            }
+        }
+        let mut missing_tables = vec![];
+        let mut stale_inventories = vec![];
+        for inventory in &inventories {
+            let Some(table) = tables.get(&table_key(inventory)) else {
+                continue;
+            };
+            if !table.exists {
+                missing_tables.push(inventory.clone());
+                continue;
+            }
+            if let (Some(snapshot_id), Some(committed_at)) = (
+                table.current_snapshot_id,
+                table
+                    .snapshot_timestamp_ms
+                    .and_then(DateTime::from_timestamp_millis),
+            ) && inventory.updated_at < committed_at
+            {
+                stale_inventories.push(StaleInventory {
+                    inventory: inventory.clone(),
+                    snapshot_id,
+                    snapshot_committed_at: committed_at,
+                });
+            }
+        }
+        let locations: BTreeSet<&IcebergLocation> =
+            keys.iter().map(|(location, _)| location).collect();
+        let orphan_registrations = registrations
+            .iter()
+            .filter(|registration| {
+                !locations.contains(&registration.iceberg_location.iceberg_location)
+            })
+            .cloned()
+            .collect();
+        let synced = match sync {
+            true => {
+                let stale: BTreeSet<TableKey> = stale_inventories
+                    .iter()
+                    .map(|stale| table_key(&stale.inventory))
+                    .collect();
+                stream::iter(stale)
+                    .map(|(iceberg_location, schema_table)| async move {
+                        let (inventories, error) = match management_client
+                            .sync_table(&iceberg_location, &schema_table)
+                            .await
+                        {
+                            Ok(inventories) => (inventories, None),
+                            Err(e) => (vec![], Some(format!("{:#}", e))),
+                        };
+                        TableSync {
+                            iceberg_location,
+                            schema_table,
+                            inventories,
+                            error,
+                        }
+                    })
+                    .buffer_unordered(TABLE_CONCURRENCY)
+                    .collect()
+                    .await
+            }
+            false => vec![],
+        };
+        Ok(Self {
+            checked_at,
+            inventories: inventories.len(),
+            registrations: registrations.len(),
+            tables: keys.len(),
+            missing_tables,
+            orphan_registrations,
+            stale_inventories,
+            unchecked_tables,
+            synced,
+        })
+    }
+    // Problems left after any sync, stale inventories whose table synced cleanly no longer count.
+    pub fn problems(&self) -> usize {
+        let synced: BTreeSet<(&IcebergLocation, &str)> = self
+            .synced
+            .iter()
+            .filter(|sync| sync.error.is_none())
+            .map(|sync| (&sync.iceberg_location, sync.schema_table.as_str()))
+            .collect();
+        let stale = self
+            .stale_inventories
+            .iter()
+            .filter(|stale| {
+                !synced.contains(&(
+                    &stale.inventory.id.iceberg_location.iceberg_location,
+                    stale.inventory.id.schema_table.schema_table.as_str(),
+                ))
+            })
+            .count();
+        self.missing_tables.len()
+            + self.orphan_registrations.len()
+            + self.unchecked_tables.len()
+            + stale
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::http::management::memory::InMemoryManagement;
+    use crate::api::store::catalog::catalog_source::CatalogSource;
+    use crate::api::store::catalog::config::{CatalogBackend, DiciConfig};
+    use iceberg::spec::Schema;
+    use iceberg::{NamespaceIdent, TableCreation};
+    use serde_json::json;
+    use std::collections::HashMap;
+    const PERMITS: &str = "_0123456789abcdef0123456789abcdef";
+    const BUDGET: &str = "_fedcba9876543210fedcba9876543210";
+    const ARCHIVE: &str = "_00000000000000000000000000000000";
+    fn inventory(location: &str, schema_table: &str) -> Inventory {
+        serde_json::from_value(json!({
+            "id": {
+                "domain": {"domain": "data.example.gov"},
+                "icebergLocation": {"icebergLocation": location},
+                "schemaTable": {"schemaTable": schema_table}
+            },
+            "fourByFour": {"fourByFour": "abcd-1234"},
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap()
+    }
+    fn registration(path: &str, location: &str) -> Registration {
+        serde_json::from_value(json!({
+            "id": {"path": path},
+            "icebergLocation": {"icebergLocation": location},
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z",
+            "metadata": {}
+        }))
+        .unwrap()
+    }
+    fn stale(schema_table: &str) -> StaleInventory {
+        StaleInventory {
+            inventory: inventory(PERMITS, schema_table),
+            snapshot_id: 1,
+            snapshot_committed_at: "2024-02-01T00:00:00Z".parse().unwrap(),
+        }
+    }
+    fn synced(schema_table: &str, error: Option<&str>) -> TableSync {
+        TableSync {
+            iceberg_location: PERMITS.parse().unwrap(),
+            schema_table: schema_table.to_string(),
+            inventories: vec![],
+            error: error.map(str::to_string),
+        }
+    }
+    // Only the permits location exists in the catalog, holding incode.permits.
+    async fn catalog() -> DiciCatalog {
+        let config = DiciConfig::builder()
+            .warehouse("memory://warehouse")
+            .backend(CatalogBackend::Memory)
+            .build();
+        let dici_catalog = DiciCatalog::builder().config(config).build();
+        let catalog = dici_catalog.catalog().await.unwrap();
+        let namespace = NamespaceIdent::new(PERMITS.to_string());
+        catalog
+            .create_namespace(&namespace, HashMap::new())
+            .await
+            .unwrap();
+        let creation = TableCreation::builder()
+            .name("incode.permits".to_string())
+            .schema(Schema::builder().build().unwrap())
+            .build();
+        catalog.create_table(&namespace, creation).await.unwrap();
+        dici_catalog
+    }
+    #[tokio::test]
+    async fn cross_checks_inventories_and_registrations_against_the_catalog() {
+        let management = InMemoryManagement::new(
+            vec![
+                inventory(PERMITS, "incode.permits"),
+                inventory(PERMITS, "incode.missing"),
+                inventory(BUDGET, "finance.budget"),
+            ],
+            vec![
+                registration("erp/permits", PERMITS),
+                registration("erp/archive", ARCHIVE),
+            ],
+        );
+        let report = AuditReport::run(&management, &catalog().await, false)
+            .await
+            .unwrap();
+        assert_eq!(report.tables, 3);
+        let missing: Vec<&str> = report
+            .missing_tables
+            .iter()
+            .map(|inventory| inventory.id.schema_table.schema_table.as_str())
+            .collect();
+        assert_eq!(missing, ["incode.missing"]);
+        assert_eq!(report.orphan_registrations.len(), 1);
+        assert_eq!(report.orphan_registrations[0].key().as_str(), "erp/archive");
+        // The catalog fails for a namespace it doesn't have, so budget couldn't be checked.
+        assert_eq!(report.unchecked_tables.len(), 1);
+        assert_eq!(report.unchecked_tables[0].schema_table, "finance.budget");
+        assert!(report.stale_inventories.is_empty());
+        assert_eq!(report.problems(), 3);
+    }
+    #[test]
+    fn cleanly_synced_tables_no_longer_count() {
+        let report = AuditReport {
+            checked_at: Utc::now(),
+            inventories: 2,
+            registrations: 0,
+            tables: 2,
+            missing_tables: vec![],
+            orphan_registrations: vec![],
+            stale_inventories: vec![stale("a.stale"), stale("a.failed")],
+            unchecked_tables: vec![],
+            synced: vec![],
+        };
+        assert_eq!(report.problems(), 2);
+        let report = AuditReport {
+            synced: vec![
+                synced("a.stale", None),
+                synced("a.failed", Some("timed out")),
+            ],
+            ..report
+        };
+        assert_eq!(report.problems(), 1);
+    }
+}
@@ -0,0 +1,265 @@
+use crate::api::audit::AuditReport;
+use crate::api::error::{DiciError, ErrorKind};
+use crate::api::http::management::api::ManagementApi;
+use crate::api::http::management::model::inventory::Inventory;
+use crate::api::identifier::{Domain, IcebergLocation};
+use crate::api::resolve::Identifier;
+use crate::api::retry::Retry;
+use anyhow::{Context, Result, bail};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::Duration;
+use typed_builder::TypedBuilder;
+// A table to sync, in every domain it is mapped to or only in `domain`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncTarget {
+    pub domain: Option<Domain>,
+    pub iceberg_location: IcebergLocation,
+    pub schema_table: String,
+}
+impl From<&Inventory> for SyncTarget {
+    fn from(inventory: &Inventory) -> Self {
+        Self {
+            domain: Some(inventory.id.domain.domain.clone()),
+            iceberg_location: inventory.id.iceberg_location.iceberg_location.clone(),
+            schema_table: inventory.id.schema_table.schema_table.clone(),
+        }
+    }
+}
+// <icebergLocation>/<schemaTable> or an inventory id <domain>/<icebergLocation>/<schemaTable>,
+// the iceberg: and inv: asset forms work too.
+impl FromStr for SyncTarget {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s.parse::<Identifier>()? {
+            Identifier::IcebergAsset {
+                iceberg_location,
+                schema_table,
+            } => Ok(Self {
+                domain: None,
+                iceberg_location,
+                schema_table,
+            }),
+            Identifier::Inventory {
+                domain,
+                iceberg_location,
+                schema_table,
+            } => Ok(Self {
+                domain: Some(domain),
+                iceberg_location,
+                schema_table,
+            }),
+            _ => bail!(
+                "Invalid sync target '{}', expected <icebergLocation>/<schemaTable> or <domain>/<icebergLocation>/<schemaTable>",
+                s
+            ),
+        }
+    }
+}
+impl Display for SyncTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.domain {
+            Some(domain) => write!(
+                f,
+                "{}/{}/{}",
+                domain, self.iceberg_location, self.schema_table
+            ),
+            None => write!(f, "{}/{}", self.iceberg_location, self.schema_table),
+        }
+    }
+}
+impl SyncTarget {
+    // One target per line, blank lines and lines starting with # are skipped.
+    pub fn parse_list(list: &str) -> Result<Vec<Self>> {
+        list.lines()
+            .enumerate()
+            .map(|(number, line)| (number + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(number, line)| {
+                line.parse()
+                    .with_context(|| format!("Invalid sync target on line {}", number))
+            })
+            .collect()
+    }
+    // Every inventory of the domain.
+    pub async fn from_domain(
+        management_client: &dyn ManagementApi,
+        domain: &Domain,
+    ) -> Result<Vec<Self>> {
+        Ok(management_client
+            .fetch_inventories_by_domain(domain)
+            .await?
+            .iter()
+            .map(Self::from)
+            .collect())
+    }
+    // The stale inventories of an audit report.
+    pub fn from_audit(report: &AuditReport) -> Vec<Self> {
+        report
+            .stale_inventories
+            .iter()
+            .map(|stale| Self::from(&stale.inventory))
+            .collect()
+    }
+}
+// A target that still failed after every retry.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncFailure {
+    pub target: SyncTarget,
+    pub attempts: u32,
+    pub reason: String,
+}
+// Shaped like IcebergLocationSync, failures name their target since a table may have no inventory yet.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkSyncReport {
+    pub successes: Vec<Inventory>,
+    pub failures: Vec<SyncFailure>,
+}
+// Reported after each target finishes, `error` is None when it synced.
+#[derive(Debug, Clone)]
+pub struct SyncProgress {
+    pub completed: usize,
+    pub total: usize,
+    pub target: SyncTarget,
+    pub attempts: u32,
+    pub error: Option<String>,
+}
+#[derive(TypedBuilder, Clone, Debug)]
+pub struct BulkSync {
+    #[builder(default = 8)]
+    concurrency: usize,
+    // Attempts after the first one, waiting `backoff` and doubling it each time. Only 5xx responses and
+    // connection failures are retried, a sync that timed out may still be running and a missing table
+    // fails right away.
+    #[builder(default = 3)]
+    retries: u32,
+    #[builder(default = Duration::from_millis(500))]
+    backoff: Duration,
+}
+impl Default for BulkSync {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+impl BulkSync {
+    // Targets without a domain go through sync_table, the others through sync_table_domain. Dici
+    // management answers sync_table for a missing table with no inventories rather than a 404.
+    async fn sync(
+        management_client: &dyn ManagementApi,
+        target: &SyncTarget,
+    ) -> Result<Vec<Inventory>> {
+        match &target.domain {
+            Some(domain) => Ok(vec![
+                management_client
+                    .sync_table_domain(domain, &target.iceberg_location, &target.schema_table)
+                    .await?,
+            ]),
+            None => {
+                let inventories = management_client
+                    .sync_table(&target.iceberg_location, &target.schema_table)
+                    .await?;
+                if inventories.is_empty() {
+                    return Err(DiciError::NotFound(format!(
+                        "Table {} not found, it has no inventory",
+                        target
+                    ))
+                    .into());
+                }
+                Ok(inventories)
+            }
+        }
+    }
+    async fn sync_with_retries(
+        &self,
+        management_client: &dyn ManagementApi,
+        target: &SyncTarget,
+    ) -> (u32, Result<Vec<Inventory>>) {
+        Retry::new(self.retries, self.backoff)
+            .run(
+                move || Self::sync(management_client, target),
+                |result| matches!(result, Err(e) if ErrorKind::of(e) == ErrorKind::Unavailable),
+            )
+            .await
+    }
+    // Syncs each distinct target once, at most `concurrency` at a time.
+    pub async fn run(
+        &self,
+        management_client: &dyn ManagementApi,
+        targets: Vec<SyncTarget>,
+        on_progress: impl Fn(&SyncProgress),
+    ) -> BulkSyncReport {
+        let targets: BTreeSet<SyncTarget> = targets.into_iter().collect();
+        let total = targets.len();
+        let mut results = stream::iter(targets)
+            .map(|target| async move {
+                let (attempts, result) = self.sync_with_retries(management_client, &target).await;
+                (target, attempts, result)
+            })
+            .buffer_unordered(self.concurrency.max(1));
+        let mut report = BulkSyncReport::default();
+        let mut completed = 0;
+        while let Some((target, attempts, result)) = results.next().await {
+            completed += 1;
+            on_progress(&SyncProgress {
+                completed,
+                total,
+                target: target.clone(),
+                attempts,
+                error: result.as_ref().err().map(|e| format!("{:#}", e)),
+            });
+            match result {
+                Ok(inventories) => report.successes.extend(inventories),
+                Err(e) => report.failures.push(SyncFailure {
+                    target,
+                    attempts,
+                    reason: format!("{:#}", e),
+                }),
+            }
+        }
+        report
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::http::management::memory::InMemoryManagement;
+    use serde_json::json;
+    const LOCATION: &str = "_0123456789abcdef0123456789abcdef";
+    fn management() -> InMemoryManagement {
+        let permits = json!({
+            "id": {
+                "domain": {"domain": "data.example.gov"},
+                "icebergLocation": {"icebergLocation": LOCATION},
+                "schemaTable": {"schemaTable": "incode.permits"}
+            },
+            "fourByFour": {"fourByFour": "abcd-1234"},
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        });
+        InMemoryManagement::new(vec![serde_json::from_value(permits).unwrap()], vec![])
+    }
+    #[tokio::test]
+    async fn missing_tables_fail_without_retries() {
+        let management = management();
+        let targets = SyncTarget::parse_list(&format!(
+            "{0}/incode.permits\n{0}/incode.missing\ndata.example.gov/{0}/incode.missing",
+            LOCATION
+        ))
+        .unwrap();
+        let bulk_sync = BulkSync::builder().backoff(Duration::ZERO).build();
+        let report = bulk_sync.run(&management, targets, |_| {}).await;
+        assert_eq!(report.successes.len(), 1);
+        assert_eq!(report.failures.len(), 2);
+        for failure in &report.failures {
+            assert_eq!(failure.target.schema_table, "incode.missing");
+            assert_eq!(failure.attempts, 1);
+            assert!(failure.reason.contains("not found"), "{}", failure.reason);
+        }
+    }
+}
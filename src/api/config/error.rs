@@ -0,0 +1,34 @@
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error(
+        "{setting} is not configured, set {env}, pass --{flag} or add `{key}` to the config profile"
+    )]
+    Missing {
+        setting: &'static str,
+        env: &'static str,
+        flag: &'static str,
+        key: &'static str,
+    },
+    #[error("Invalid {setting}: {reason}")]
+    Invalid {
+        setting: &'static str,
+        reason: String,
+    },
+    #[error("Failed to read config file {path}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to parse config file {path}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+    #[error("Profile '{0}' is not defined in the config file")]
+    UnknownProfile(String),
+}
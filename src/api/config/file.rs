@@ -0,0 +1,67 @@
+use crate::api::config::error::ConfigError;
+use crate::api::http::management::config::{ManagementAddress, ManagementOptions};
+use crate::api::store::catalog::config::{CatalogBackend, GlueOptions, S3Options, Warehouse};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+#[derive(Deserialize, Default, Clone, Debug)]
+pub struct Profile {
+    pub warehouse: Option<Warehouse>,
+    pub management_address: Option<ManagementAddress>,
+    #[serde(default)]
+    pub management: ManagementOptions,
+    pub management_snapshot: Option<PathBuf>,
+    pub catalog: Option<CatalogBackend>,
+    #[serde(default)]
+    pub glue: GlueOptions,
+    #[serde(default)]
+    pub s3: S3Options,
+    pub table_cache_ttl_seconds: Option<u64>,
+}
+impl Profile {
+    pub fn merge(self, other: Profile) -> Profile {
+        Profile {
+            warehouse: other.warehouse.or(self.warehouse),
+            management_address: other.management_address.or(self.management_address),
+            management: self.management.merge(other.management),
+            management_snapshot: other.management_snapshot.or(self.management_snapshot),
+            catalog: other.catalog.or(self.catalog),
+            glue: self.glue.merge(other.glue),
+            s3: self.s3.merge(other.s3),
+            table_cache_ttl_seconds: other
+                .table_cache_ttl_seconds
+                .or(self.table_cache_ttl_seconds),
+        }
+    }
+}
+#[derive(Deserialize, Default, Clone, Debug)]
+pub struct ConfigFile {
+    pub default_profile: Option<String>,
+    #[serde(flatten)]
+    pub base: Profile,
+    #[serde(default)]
+    pub profiles: HashMap<String, Profile>,
+}
+impl ConfigFile {
+    pub fn read(path: &Path) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        toml::from_str(&contents).map_err(|source| ConfigError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+    pub fn profile(&self, name: Option<&str>) -> Result<Profile, ConfigError> {
+        match name.or(self.default_profile.as_deref()) {
+            Some(name) => self
+                .profiles
+                .get(name)
+                .cloned()
+                .map(|profile| self.base.clone().merge(profile))
+                .ok_or_else(|| ConfigError::UnknownProfile(name.to_string())),
+            None => Ok(self.base.clone()),
+        }
+    }
+}
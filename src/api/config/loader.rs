@@ -0,0 +1,86 @@
+use crate::api::config::error::ConfigError;
+use crate::api::config::file::{ConfigFile, Profile};
+use crate::api::config::settings::DiciSettings;
+use crate::api::http::management::config::{
+    ManagementConfig, management_address_from_env, management_options_from_env,
+    management_snapshot_from_env,
+};
+use crate::api::store::catalog::config::{
+    DiciConfig, backend_from_env, glue_options_from_env, s3_options_from_env, warehouse_from_env,
+};
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+use typed_builder::TypedBuilder;
+fn config_path_from_env() -> Option<PathBuf> {
+    env::var("DICI_CONFIG").ok().map(PathBuf::from)
+}
+fn profile_from_env() -> Option<String> {
+    env::var("DICI_PROFILE").ok()
+}
+fn default_config_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".config").join("dici").join("config.toml"))
+}
+fn profile_from_env_vars() -> Result<Profile, ConfigError> {
+    Ok(Profile {
+        warehouse: warehouse_from_env(),
+        management_address: management_address_from_env(),
+        management: management_options_from_env()?,
+        management_snapshot: management_snapshot_from_env(),
+        catalog: backend_from_env()?,
+        glue: glue_options_from_env(),
+        s3: s3_options_from_env()?,
+        table_cache_ttl_seconds: None,
+    })
+}
+// Layers from lowest to highest precedence: defaults (per command), config file, selected profile,
+// environment, overrides (cli flags).
+#[derive(TypedBuilder, Default, Clone)]
+pub struct ConfigLoader {
+    #[builder(default, setter(into, strip_option(fallback = path_opt)))]
+    path: Option<PathBuf>,
+    #[builder(default, setter(into, strip_option(fallback = profile_opt)))]
+    profile: Option<String>,
+    #[builder(default)]
+    defaults: Profile,
+    #[builder(default)]
+    overrides: Profile,
+}
+impl ConfigLoader {
+    fn config_file(&self) -> Result<ConfigFile, ConfigError> {
+        match self.path.clone().or_else(config_path_from_env) {
+            Some(path) => ConfigFile::read(&path),
+            None => match default_config_path() {
+                Some(path) if path.exists() => ConfigFile::read(&path),
+                _ => Ok(ConfigFile::default()),
+            },
+        }
+    }
+    pub fn load(&self) -> Result<DiciSettings, ConfigError> {
+        let profile_name = self.profile.clone().or_else(profile_from_env);
+        let profile = self
+            .defaults
+            .clone()
+            .merge(self.config_file()?.profile(profile_name.as_deref())?)
+            .merge(profile_from_env_vars()?)
+            .merge(self.overrides.clone());
+        Ok(DiciSettings::builder()
+            .dici(
+                DiciConfig::builder()
+                    .warehouse_opt(profile.warehouse)
+                    .backend_opt(profile.catalog)
+                    .glue(profile.glue)
+                    .s3(profile.s3)
+                    .table_cache_ttl_opt(profile.table_cache_ttl_seconds.map(Duration::from_secs))
+                    .build(),
+            )
+            .management(
+                ManagementConfig::builder()
+                    .address_opt(profile.management_address)
+                    .options(profile.management)
+                    .snapshot_opt(profile.management_snapshot)
+                    .build(),
+            )
+            .build())
+    }
+}
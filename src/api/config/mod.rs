@@ -0,0 +1,4 @@
+pub mod error;
+pub mod file;
+pub mod loader;
+pub mod settings;
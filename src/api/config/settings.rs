@@ -0,0 +1,10 @@
+use crate::api::http::management::config::ManagementConfig;
+use crate::api::store::catalog::config::DiciConfig;
+use typed_builder::TypedBuilder;
+#[derive(TypedBuilder, Default, Clone)]
+pub struct DiciSettings {
+    #[builder(default)]
+    pub dici: DiciConfig,
+    #[builder(default)]
+    pub management: ManagementConfig,
+}
@@ -0,0 +1,171 @@
+use crate::api::audit::AuditReport;
+use crate::api::config::settings::DiciSettings;
+use crate::api::error::DiciError;
+use crate::api::http::management::api::{ManagementApi, default_management_api};
+use crate::api::http::management::client::ManagementClient;
+use crate::api::http::management::snapshot::SnapshotManagement;
+use crate::api::identifier::IdentifierError;
+use crate::api::resolve::{Identifier, IdentityGraph};
+use crate::api::store::asset::core::{CoreAsset, CoreFxf};
+use crate::api::store::asset::dici::{
+    CoreArgs, DiciAsset, IcebergArgs, InventoryArgs, RegistrationArgs,
+};
+use crate::api::store::asset::iceberg::{IcebergAsset, IcebergLocation, IcebergSchemaTable};
+use crate::api::store::asset::inventory::{InventoryAsset, InventoryDomain};
+use crate::api::store::asset::registration::{RegistrationAsset, RegistrationPath};
+use crate::api::store::asset::version::TableVersion;
+use crate::api::store::catalog::dici::DiciCatalog;
+use crate::api::store::session::DiciSession;
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::sync::Arc;
+use typed_builder::TypedBuilder;
+#[derive(TypedBuilder, Clone)]
+pub struct DiciContext {
+    #[builder(default = default_management_api(), setter(into))]
+    pub management_client: Arc<dyn ManagementApi>,
+    #[builder(default)]
+    pub dici_catalog: DiciCatalog,
+}
+impl Default for DiciContext {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+impl From<DiciSettings> for DiciContext {
+    fn from(settings: DiciSettings) -> Self {
+        let management_client: Arc<dyn ManagementApi> = match &settings.management.snapshot {
+            Some(path) => SnapshotManagement::new(path).into(),
+            None => ManagementClient::builder()
+                .config(settings.management)
+                .build()
+                .into(),
+        };
+        Self::builder()
+            .management_client(management_client)
+            .dici_catalog(DiciCatalog::builder().config(settings.dici).build())
+            .build()
+    }
+}
+impl DiciContext {
+    pub fn core_asset(&self, fxf: CoreFxf) -> DiciAsset {
+        CoreArgs::builder()
+            .asset(CoreAsset::builder().fxf(fxf).build())
+            .dici_catalog(self.dici_catalog.clone())
+            .management_client(self.management_client.clone())
+            .build()
+            .into()
+    }
+    pub fn iceberg_asset(
+        &self,
+        iceberg_location: IcebergLocation,
+        iceberg_schema_table: IcebergSchemaTable,
+    ) -> DiciAsset {
+        IcebergArgs::builder()
+            .asset(
+                IcebergAsset::builder()
+                    .location(iceberg_location)
+                    .schema_table(iceberg_schema_table)
+                    .build(),
+            )
+            .dici_catalog(self.dici_catalog.clone())
+            .build()
+            .into()
+    }
+    pub fn inventory_asset(
+        &self,
+        domain: InventoryDomain,
+        iceberg_location: IcebergLocation,
+        iceberg_schema_table: IcebergSchemaTable,
+    ) -> DiciAsset {
+        InventoryArgs::builder()
+            .asset(
+                InventoryAsset::builder()
+                    .domain(domain)
+                    .location(iceberg_location)
+                    .schema_table(iceberg_schema_table)
+                    .build(),
+            )
+            .dici_catalog(self.dici_catalog.clone())
+            .management_client(self.management_client.clone())
+            .build()
+            .into()
+    }
+    pub fn registration_asset(
+        &self,
+        path: RegistrationPath,
+        iceberg_schema_table: IcebergSchemaTable,
+    ) -> DiciAsset {
+        RegistrationArgs::builder()
+            .asset(
+                RegistrationAsset::builder()
+                    .path(path)
+                    .schema_table(iceberg_schema_table)
+                    .build(),
+            )
+            .dici_catalog(self.dici_catalog.clone())
+            .management_client(self.management_client.clone())
+            .build()
+            .into()
+    }
+    // Everything connected to an identifier, see IdentityGraph.
+    pub async fn resolve(&self, identifier: Identifier) -> Result<IdentityGraph> {
+        IdentityGraph::resolve(
+            identifier,
+            self.management_client.as_ref(),
+            &self.dici_catalog,
+        )
+        .await
+    }
+    // Cross-checks management against the catalog, see AuditReport::run.
+    pub async fn audit(&self, sync: bool) -> Result<AuditReport> {
+        AuditReport::run(self.management_client.as_ref(), &self.dici_catalog, sync).await
+    }
+    // A session where core and iceberg tables resolve on demand, see DiciSession::register_providers.
+    pub fn session(&self) -> Result<DiciSession> {
+        self.session_at(&TableVersion::Current)
+    }
+    // Like session, with every table resolved on demand read at the given version.
+    pub fn session_at(&self, version: &TableVersion) -> Result<DiciSession> {
+        let session = DiciSession::default();
+        session.register_providers(&self.management_client, &self.dici_catalog, version)?;
+        Ok(session)
+    }
+    // Binds an asset, e.g. one parsed from its string form, to this context's clients.
+    pub fn asset(&self, asset: DiciAsset) -> DiciAsset {
+        asset.with_clients(&self.dici_catalog, &self.management_client)
+    }
+    // Parses an asset in the form described on DiciAsset's FromStr.
+    pub fn parse_asset(&self, spec: &str) -> Result<DiciAsset> {
+        Ok(self.asset(spec.parse()?))
+    }
+    // Parses `<alias>=<asset>`, see parse_asset.
+    pub fn parse_aliased_asset(&self, spec: &str) -> Result<(String, DiciAsset)> {
+        let (alias, asset) = spec
+            .split_once('=')
+            .filter(|(alias, _)| !alias.is_empty())
+            .with_context(|| {
+                format!("Invalid aliased asset '{}', expected <alias>=<asset>", spec)
+            })?;
+        Ok((alias.to_string(), self.parse_asset(asset)?))
+    }
+    // Parses every `<alias>=<asset>` and reads each asset at `version`. An alias given twice is refused
+    // rather than one asset silently replacing the other.
+    pub fn parse_aliased_assets(
+        &self,
+        specs: &[String],
+        version: &TableVersion,
+    ) -> Result<Vec<(String, DiciAsset)>> {
+        let mut aliases = HashSet::new();
+        specs
+            .iter()
+            .map(|spec| {
+                let (alias, asset) = self.parse_aliased_asset(spec)?;
+                if !aliases.insert(alias.clone()) {
+                    return Err(DiciError::from(IdentifierError::DuplicateAlias(alias)).into());
+                }
+                Ok((alias, asset.with_version(version.clone())))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,141 @@
+use crate::api::config::error::ConfigError;
+use crate::api::identifier::IdentifierError;
+use crate::api::store::asset::error::AssetParseError;
+use datafusion::error::DataFusionError;
+use serde::Serialize;
+use thiserror::Error;
+// Failures callers are expected to tell apart, the rest of the library wraps them in anyhow context.
+#[derive(Error, Debug)]
+pub enum DiciError {
+    #[error("{0}")]
+    NotFound(String),
+    #[error("Dici management refused the credentials ({status}): {body}")]
+    Unauthorized { status: u16, body: String },
+    #[error(transparent)]
+    BadIdentifier(#[from] IdentifierError),
+    #[error("Dici management returned {status}: {body}")]
+    ManagementStatus { status: u16, body: String },
+    // A 5xx response still failing after the retries.
+    #[error("Dici management is unavailable ({status}): {body}")]
+    Unavailable { status: u16, body: String },
+    #[error(transparent)]
+    Catalog(#[from] iceberg::Error),
+    #[error(transparent)]
+    Query(#[from] DataFusionError),
+    #[error("{0}")]
+    Timeout(String),
+}
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    NotFound,
+    Unauthorized,
+    BadIdentifier,
+    ManagementStatus,
+    // Dici management can't be reached or keeps failing, worth trying again later.
+    Unavailable,
+    Catalog,
+    Query,
+    Timeout,
+    Config,
+    Other,
+}
+impl DiciError {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            DiciError::NotFound(_) => ErrorKind::NotFound,
+            DiciError::Unauthorized { .. } => ErrorKind::Unauthorized,
+            DiciError::BadIdentifier(_) => ErrorKind::BadIdentifier,
+            DiciError::ManagementStatus { .. } => ErrorKind::ManagementStatus,
+            DiciError::Unavailable { .. } => ErrorKind::Unavailable,
+            DiciError::Catalog(_) => ErrorKind::Catalog,
+            DiciError::Query(_) => ErrorKind::Query,
+            DiciError::Timeout(_) => ErrorKind::Timeout,
+        }
+    }
+}
+impl ErrorKind {
+    // Walks the chain from the root cause up, so a catalog error raised inside a query counts as a
+    // catalog error and errors the library didn't raise itself are still recognised.
+    pub fn of(error: &anyhow::Error) -> Self {
+        let chain: Vec<&(dyn std::error::Error + 'static)> = error.chain().collect();
+        chain
+            .into_iter()
+            .rev()
+            .find_map(|cause| {
+                if let Some(e) = cause.downcast_ref::<DiciError>() {
+                    return Some(e.kind());
+                }
+                if cause.is::<IdentifierError>() || cause.is::<AssetParseError>() {
+                    return Some(ErrorKind::BadIdentifier);
+                }
+                if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+                    if e.is_timeout() {
+                        return Some(ErrorKind::Timeout);
+                    }
+                    return (e.is_connect() || e.is_request()).then_some(ErrorKind::Unavailable);
+                }
+                if cause.is::<tokio::time::error::Elapsed>() {
+                    return Some(ErrorKind::Timeout);
+                }
+                if cause.is::<iceberg::Error>() {
+                    return Some(ErrorKind::Catalog);
+                }
+                if cause.is::<DataFusionError>() {
+                    return Some(ErrorKind::Query);
+                }
+                if cause.is::<ConfigError>() {
+                    return Some(ErrorKind::Config);
+                }
+                None
+            })
+            .unwrap_or(ErrorKind::Other)
+    }
+    // Failures that may go away on their own, the only ones worth retrying.
+    pub fn transient(self) -> bool {
+        matches!(self, ErrorKind::Timeout | ErrorKind::Unavailable)
+    }
+    // sysexits.h codes, so scripts can tell a missing dataset from an unreachable service.
+    pub fn exit_code(self) -> u8 {
+        match self {
+            ErrorKind::BadIdentifier => 65,
+            ErrorKind::NotFound => 66,
+            ErrorKind::ManagementStatus | ErrorKind::Unavailable => 69,
+            ErrorKind::Query => 70,
+            ErrorKind::Catalog => 74,
+            ErrorKind::Timeout => 75,
+            ErrorKind::Unauthorized => 77,
+            ErrorKind::Config => 78,
+            ErrorKind::Other => 1,
+        }
+    }
+}
+// The machine readable form of an error, written to stderr by the cli and attached to mcp errors.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorReport {
+    pub kind: ErrorKind,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+impl From<&anyhow::Error> for ErrorReport {
+    fn from(error: &anyhow::Error) -> Self {
+        let (status, body) = match error.chain().find_map(|cause| cause.downcast_ref()) {
+            Some(
+                DiciError::Unauthorized { status, body }
+                | DiciError::ManagementStatus { status, body }
+                | DiciError::Unavailable { status, body },
+            ) => (Some(*status), Some(body.clone())),
+            _ => (None, None),
+        };
+        Self {
+            kind: ErrorKind::of(error),
+            message: format!("{:#}", error),
+            status,
+            body,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+// Written next to the target and renamed over it, so a crash never leaves half a file. A private file is
+// only readable by its owner.
+pub fn write_atomic(path: &Path, contents: &[u8], private: bool) -> std::io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    if private {
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    options.open(&tmp)?.write_all(contents)?;
+    std::fs::rename(&tmp, path)
+}
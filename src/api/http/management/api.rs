@@ -0,0 +1,87 @@
+use crate::api::http::management::client::ManagementClient;
+use crate::api::http::management::memory::InMemoryManagement;
+use crate::api::http::management::model::inventory::Inventory;
+use crate::api::http::management::model::registration::Registration;
+use crate::api::http::management::model::sync::IcebergLocationSync;
+use crate::api::http::management::model::version::GitConfig;
+use crate::api::http::management::snapshot::SnapshotManagement;
+use crate::api::identifier::{Domain, FourByFour, IcebergLocation, RegistrationPath};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+// Everything dici asks of dici management. Lookups that find nothing return an empty list, single
+// lookups fail with DiciError::NotFound.
+#[async_trait]
+pub trait ManagementApi: Send + Sync {
+    async fn fetch_inventories(&self) -> Result<Vec<Inventory>>;
+    async fn fetch_inventory_by_fxf(&self, fxf: &FourByFour) -> Result<Inventory>;
+    async fn fetch_inventories_by_iceberg_location(
+        &self,
+        iceberg_location: &IcebergLocation,
+    ) -> Result<Vec<Inventory>>;
+    async fn fetch_registrations(&self) -> Result<Vec<Registration>>;
+    async fn fetch_registrations_by_path(
+        &self,
+        path: &RegistrationPath,
+    ) -> Result<Vec<Registration>>;
+    async fn fetch_registrations_by_path_and_metadata(
+        &self,
+        path: &RegistrationPath,
+        metadata: &HashMap<String, String>,
+    ) -> Result<Vec<Registration>>;
+    async fn fetch_registration_by_iceberg_location(
+        &self,
+        iceberg_location: &IcebergLocation,
+    ) -> Result<Registration>;
+    async fn fetch_inventories_by_domain(&self, domain: &Domain) -> Result<Vec<Inventory>>;
+    async fn fetch_version(&self) -> Result<GitConfig>;
+    async fn fetch_inventories_updated_since(&self, since: DateTime<Utc>)
+    -> Result<Vec<Inventory>>;
+    async fn fetch_inventories_by_iceberg_location_and_table(
+        &self,
+        iceberg_location: &IcebergLocation,
+        schema_table: &str,
+    ) -> Result<Vec<Inventory>>;
+    async fn fetch_inventory_by_id(
+        &self,
+        domain: &Domain,
+        iceberg_location: &IcebergLocation,
+        schema_table: &str,
+    ) -> Result<Inventory>;
+    async fn sync_table(
+        &self,
+        iceberg_location: &IcebergLocation,
+        schema_table: &str,
+    ) -> Result<Vec<Inventory>>;
+    async fn sync_table_domain(
+        &self,
+        domain: &Domain,
+        iceberg_location: &IcebergLocation,
+        schema_table: &str,
+    ) -> Result<Inventory>;
+    async fn sync_iceberg_location(
+        &self,
+        iceberg_location: &IcebergLocation,
+    ) -> Result<IcebergLocationSync>;
+}
+// The http client, used wherever no other implementation is given.
+pub fn default_management_api() -> Arc<dyn ManagementApi> {
+    Arc::new(ManagementClient::default())
+}
+impl From<ManagementClient> for Arc<dyn ManagementApi> {
+    fn from(management_client: ManagementClient) -> Self {
+        Arc::new(management_client)
+    }
+}
+impl From<InMemoryManagement> for Arc<dyn ManagementApi> {
+    fn from(management: InMemoryManagement) -> Self {
+        Arc::new(management)
+    }
+}
+impl From<SnapshotManagement> for Arc<dyn ManagementApi> {
+    fn from(management: SnapshotManagement) -> Self {
+        Arc::new(management)
+    }
+}
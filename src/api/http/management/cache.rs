@@ -0,0 +1,372 @@
+use crate::api::fs::write_atomic;
+use crate::api::http::management::buffer_response;
+use crate::api::lock::lock;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use reqwest::header::{
+    ETAG, HeaderName, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use reqwest::{Request, Response};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+// Seconds a response stays fresh per endpoint, 0 is never cached. Updated since is what dici watch
+// polls, with a new timestamp each time.
+const DEFAULT_TTLS: [(&str, u64); 4] = [
+    ("inventories", 60),
+    ("inventories_updated", 0),
+    ("registrations", 300),
+    ("version", 3600),
+];
+// Responses the memory cache holds, the one stored longest ago makes room for a new one.
+const MAX_MEMORY_ENTRIES: usize = 1024;
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheMode {
+    Off,
+    // Kept for the life of the client, like the mcp server.
+    Memory,
+    // Shared between processes, like separate cli invocations.
+    Disk,
+}
+impl FromStr for CacheMode {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(CacheMode::Off),
+            "memory" => Ok(CacheMode::Memory),
+            "disk" => Ok(CacheMode::Disk),
+            _ => Err(format!("expected off, memory or disk, got {}", s)),
+        }
+    }
+}
+impl Display for CacheMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CacheMode::Off => "off",
+            CacheMode::Memory => "memory",
+            CacheMode::Disk => "disk",
+        })
+    }
+}
+// endpoint=seconds pairs separated by commas, e.g. inventories=30,version=86400.
+pub fn parse_ttls(value: &str) -> Result<BTreeMap<String, u64>, String> {
+    value
+        .split(',')
+        .filter(|pair| !pair.trim().is_empty())
+        .map(|pair| {
+            let (endpoint, seconds) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected endpoint=seconds, got {}", pair))?;
+            let seconds = seconds
+                .trim()
+                .parse()
+                .map_err(|_| format!("expected seconds for {}, got {}", endpoint, seconds))?;
+            Ok((endpoint.trim().to_string(), seconds))
+        })
+        .collect()
+}
+pub fn default_cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|cache| cache.join("dici").join("management"))
+}
+// What a request to dici management does, by its path below the management address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endpoint {
+    // A GET lookup, cached for the ttl configured under this name.
+    Lookup(&'static str),
+    // Changes dici management, every cached lookup may be stale afterwards.
+    Sync,
+    Other,
+}
+impl Endpoint {
+    pub fn of<'a>(request: &Request, mut segments: impl Iterator<Item = &'a str>) -> Self {
+        let lookup = request.method() == reqwest::Method::GET;
+        match (segments.next(), segments.next()) {
+            (Some("sync"), _) => Endpoint::Sync,
+            (Some("inventory"), Some("updated")) if lookup => {
+                Endpoint::Lookup("inventories_updated")
+            }
+            (Some("inventory"), _) if lookup => Endpoint::Lookup("inventories"),
+            (Some("registration" | "query"), _) if lookup => Endpoint::Lookup("registrations"),
+            (Some("version"), None) if lookup => Endpoint::Lookup("version"),
+            _ => Endpoint::Other,
+        }
+    }
+}
+// A successful response with its validators, answered again while fresh and revalidated after.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedResponse {
+    pub key: String,
+    pub stored_at: DateTime<Utc>,
+    pub status: u16,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: String,
+}
+impl CachedResponse {
+    pub async fn store(key: &str, response: Response) -> Result<(Self, Response)> {
+        buffer_response(response, |status, headers, bytes| {
+            let header = |name: HeaderName| {
+                headers
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string)
+            };
+            Self {
+                key: key.to_string(),
+                stored_at: Utc::now(),
+                status: status.as_u16(),
+                etag: header(ETAG),
+                last_modified: header(LAST_MODIFIED),
+                body: String::from_utf8_lossy(bytes).into_owned(),
+            }
+        })
+        .await
+    }
+    pub fn fresh(&self, ttl: Duration) -> bool {
+        (Utc::now() - self.stored_at)
+            .to_std()
+            .is_ok_and(|age| age < ttl)
+    }
+    // Asks dici management to answer 304 Not Modified if the cached body is still current.
+    pub fn revalidate(&self, request: &mut Request) {
+        let validators = [
+            (IF_NONE_MATCH, &self.etag),
+            (IF_MODIFIED_SINCE, &self.last_modified),
+        ];
+        for (name, value) in validators {
+            if let Some(value) = value.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+                request.headers_mut().insert(name, value);
+            }
+        }
+    }
+    pub fn refreshed(self) -> Self {
+        Self {
+            stored_at: Utc::now(),
+            ..self
+        }
+    }
+    pub fn response(&self) -> Result<Response> {
+        let mut response = http::Response::builder().status(self.status);
+        if let Some(etag) = &self.etag {
+            response = response.header(ETAG, etag);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            response = response.header(LAST_MODIFIED, last_modified);
+        }
+        Ok(Response::from(response.body(self.body.clone())?))
+    }
+}
+// 64 bit FNV-1a, stable between builds so separate processes find the same file.
+fn file_name(key: &str) -> String {
+    let hash = key.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}.json", hash)
+}
+// Cached bodies hold whatever dici management answered, so only the owner may list or read them.
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(dir)
+}
+enum CacheStore {
+    Memory(Mutex<HashMap<String, CachedResponse>>),
+    Disk(PathBuf),
+}
+// Responses of dici management lookups keyed by a hash of their credentials and their url. Failing to read or write the disk cache
+// only costs a request, so those errors are ignored.
+pub struct ResponseCache {
+    store: CacheStore,
+    ttls: BTreeMap<String, Duration>,
+}
+impl ResponseCache {
+    pub fn new(
+        mode: CacheMode,
+        dir: Option<PathBuf>,
+        ttl_seconds: &BTreeMap<String, u64>,
+    ) -> Option<Self> {
+        let store = match mode {
+            CacheMode::Off => return None,
+            CacheMode::Memory => CacheStore::Memory(Mutex::default()),
+            CacheMode::Disk => match dir.or_else(default_cache_dir) {
+                Some(dir) => CacheStore::Disk(dir),
+                None => CacheStore::Memory(Mutex::default()),
+            },
+        };
+        let ttls = DEFAULT_TTLS
+            .into_iter()
+            .map(|(endpoint, seconds)| (endpoint.to_string(), seconds))
+            .chain(ttl_seconds.clone())
+            .map(|(endpoint, seconds)| (endpoint, Duration::from_secs(seconds)))
+            .collect();
+        Some(Self { store, ttls })
+    }
+    pub fn ttl(&self, endpoint: &str) -> Duration {
+        self.ttls.get(endpoint).copied().unwrap_or_default()
+    }
+    pub fn get(&self, key: &str) -> Option<CachedResponse> {
+        match &self.store {
+            CacheStore::Memory(entries) => lock(entries).get(key).cloned(),
+            CacheStore::Disk(dir) => {
+                let contents = std::fs::read_to_string(dir.join(file_name(key))).ok()?;
+                serde_json::from_str::<CachedResponse>(&contents)
+                    .ok()
+                    .filter(|cached| cached.key == key)
+            }
+        }
+    }
+    pub fn put(&self, cached: CachedResponse) {
+        match &self.store {
+            CacheStore::Memory(entries) => {
+                let mut entries = lock(entries);
+                if entries.len() >= MAX_MEMORY_ENTRIES && !entries.contains_key(&cached.key) {
+                    let oldest = entries
+                        .values()
+                        .min_by_key(|entry| entry.stored_at)
+                        .map(|entry| entry.key.clone());
+                    if let Some(oldest) = oldest {
+                        entries.remove(&oldest);
+                    }
+                }
+                entries.insert(cached.key.clone(), cached);
+            }
+            CacheStore::Disk(dir) => {
+                let path = dir.join(file_name(&cached.key));
+                let _ = create_private_dir(dir)
+                    .and_then(|_| write_atomic(&path, &serde_json::to_vec(&cached)?, true));
+            }
+        }
+    }
+    // Only removes files named like cache entries, the directory may be shared.
+    pub fn clear(&self) {
+        match &self.store {
+            CacheStore::Memory(entries) => {
+                lock(entries).clear();
+            }
+            CacheStore::Disk(dir) => {
+                let Ok(files) = std::fs::read_dir(dir) else {
+                    return;
+                };
+                for file in files.flatten() {
+                    let name = file.file_name();
+                    let is_entry = name.to_str().is_some_and(|name| {
+                        name.strip_suffix(".json").is_some_and(|hash| {
+                            hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit())
+                        })
+                    });
+                    if is_entry {
+                        let _ = std::fs::remove_file(file.path());
+                    }
+                }
+            }
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::{Method, Url};
+    fn endpoint(method: Method, path: &str) -> Endpoint {
+        let request = Request::new(method, Url::parse("http://localhost/").unwrap());
+        Endpoint::of(&request, path.split('/').filter(|s| !s.is_empty()))
+    }
+    fn cached(key: &str) -> CachedResponse {
+        CachedResponse {
+            key: key.to_string(),
+            stored_at: Utc::now(),
+            status: 200,
+            etag: Some("\"v1\"".to_string()),
+            last_modified: None,
+            body: "[]".to_string(),
+        }
+    }
+    #[test]
+    fn classifies_lookups_and_syncs() {
+        let lookups = [
+            ("inventory", "inventories"),
+            ("inventory/fxf/abcd-1234", "inventories"),
+            ("inventory/updated", "inventories_updated"),
+            ("registration", "registrations"),
+            ("query/erp/incode", "registrations"),
+            ("version", "version"),
+        ];
+        for (path, ttl) in lookups {
+            assert_eq!(
+                endpoint(Method::GET, path),
+                Endpoint::Lookup(ttl),
+                "{}",
+                path
+            );
+        }
+        assert_eq!(endpoint(Method::POST, "query/erp"), Endpoint::Other);
+        assert_eq!(endpoint(Method::GET, "version/extra"), Endpoint::Other);
+        assert_eq!(endpoint(Method::GET, "health"), Endpoint::Other);
+        assert_eq!(endpoint(Method::POST, "sync"), Endpoint::Sync);
+        assert_eq!(endpoint(Method::POST, "sync/domain"), Endpoint::Sync);
+        assert_eq!(endpoint(Method::GET, "sync/iceberg/_0123"), Endpoint::Sync);
+    }
+    #[test]
+    fn configured_ttls_replace_the_defaults() {
+        assert!(ResponseCache::new(CacheMode::Off, None, &BTreeMap::new()).is_none());
+        let ttls = parse_ttls("inventories=5, other = 7").unwrap();
+        let cache = ResponseCache::new(CacheMode::Memory, None, &ttls).unwrap();
+        assert_eq!(cache.ttl("inventories"), Duration::from_secs(5));
+        assert_eq!(cache.ttl("other"), Duration::from_secs(7));
+        assert_eq!(cache.ttl("version"), Duration::from_secs(3600));
+        assert_eq!(cache.ttl("unknown"), Duration::ZERO);
+        assert!(parse_ttls("inventories").is_err());
+        assert!(parse_ttls("inventories=soon").is_err());
+    }
+    #[test]
+    fn disk_entries_are_private_and_cleared() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("management");
+        let cache =
+            ResponseCache::new(CacheMode::Disk, Some(dir.clone()), &BTreeMap::new()).unwrap();
+        cache.put(cached("a@http://localhost/version"));
+        assert!(cache.get("b@http://localhost/version").is_none());
+        let entry = cache.get("a@http://localhost/version").unwrap();
+        assert_eq!(entry.etag.as_deref(), Some("\"v1\""));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(&dir), 0o700);
+            for file in std::fs::read_dir(&dir).unwrap() {
+                assert_eq!(mode(&file.unwrap().path()), 0o600);
+            }
+        }
+        std::fs::write(dir.join("notes.json"), "{}").unwrap();
+        cache.clear();
+        assert!(cache.get("a@http://localhost/version").is_none());
+        assert!(dir.join("notes.json").exists());
+    }
+    #[test]
+    fn memory_drops_the_oldest_response_once_full() {
+        let cache = ResponseCache::new(CacheMode::Memory, None, &BTreeMap::new()).unwrap();
+        let start = Utc::now();
+        for i in 0..=MAX_MEMORY_ENTRIES {
+            cache.put(CachedResponse {
+                stored_at: start + chrono::Duration::seconds(i as i64),
+                ..cached(&format!("a@http://localhost/inventory/{i}"))
+            });
+        }
+        assert!(cache.get("a@http://localhost/inventory/0").is_none());
+        assert!(cache.get("a@http://localhost/inventory/1").is_some());
+        assert!(
+            cache
+                .get(&format!(
+                    "a@http://localhost/inventory/{MAX_MEMORY_ENTRIES}"
+                ))
+                .is_some()
+        );
+    }
+}
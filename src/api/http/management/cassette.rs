@@ -0,0 +1,322 @@
+use crate::api::fs::write_atomic;
+use crate::api::http::management::buffer_response;
+use crate::api::lock::lock;
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use reqwest::header::{
+    CONNECTION, CONTENT_ENCODING, CONTENT_LENGTH, SET_COOKIE, TRANSFER_ENCODING,
+};
+use reqwest::{Request, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Mutex, MutexGuard};
+// Headers describing the transfer rather than the answer, replayed bodies are re-encoded.
+const SKIPPED_HEADERS: [reqwest::header::HeaderName; 5] = [
+    CONNECTION,
+    CONTENT_ENCODING,
+    CONTENT_LENGTH,
+    SET_COOKIE,
+    TRANSFER_ENCODING,
+];
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CassetteMode {
+    // Replay when the cassette exists, record it otherwise.
+    #[default]
+    Auto,
+    Record,
+    Replay,
+}
+impl FromStr for CassetteMode {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(CassetteMode::Auto),
+            "record" => Ok(CassetteMode::Record),
+            "replay" => Ok(CassetteMode::Replay),
+            _ => Err(format!("expected auto, record or replay, got {}", s)),
+        }
+    }
+}
+impl Display for CassetteMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CassetteMode::Auto => "auto",
+            CassetteMode::Record => "record",
+            CassetteMode::Replay => "replay",
+        })
+    }
+}
+// Json bodies are kept as json so cassettes can be read and redacted by hand, anything else as a string.
+fn body_value(bytes: &[u8]) -> Value {
+    match bytes.is_empty() {
+        true => Value::Null,
+        false => serde_json::from_slice(bytes)
+            .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(bytes).into_owned())),
+    }
+}
+fn body_bytes(body: &Value) -> Result<Vec<u8>> {
+    Ok(match body {
+        Value::Null => vec![],
+        Value::String(text) => text.clone().into_bytes(),
+        json => serde_json::to_vec(json)?,
+    })
+}
+// Credentials are sent as headers, which are never recorded.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub body: Value,
+}
+impl From<&Request> for RecordedRequest {
+    fn from(request: &Request) -> Self {
+        Self {
+            method: request.method().to_string(),
+            path: request.url().path().to_string(),
+            query: request.url().query().map(str::to_string),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(body_value)
+                .unwrap_or(Value::Null),
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub body: Value,
+}
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CassetteFile {
+    pub recorded_at: Option<DateTime<Utc>>,
+    // The management address at recording time, used when replaying without one.
+    pub address: Option<String>,
+    #[serde(default)]
+    pub interactions: Vec<Interaction>,
+}
+// Request and response pairs of dici management traffic. Recording rewrites the file after every
+// response, replaying answers without the network and fails for requests that were never recorded.
+pub struct Cassette {
+    path: PathBuf,
+    replaying: bool,
+    file: Mutex<CassetteFile>,
+    played: Mutex<HashSet<usize>>,
+}
+impl Cassette {
+    pub fn open(path: &Path, mode: CassetteMode, address: Option<String>) -> Result<Self> {
+        let replaying = match mode {
+            CassetteMode::Auto => path.exists(),
+            CassetteMode::Record => false,
+            CassetteMode::Replay => true,
+        };
+        let file = match replaying {
+            true => {
+                let contents = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read cassette {}", path.display()))?;
+                serde_json::from_str(&contents)
+                    .with_context(|| format!("Failed to parse cassette {}", path.display()))?
+            }
+            false => CassetteFile {
+                recorded_at: Some(Utc::now()),
+                address,
+                interactions: vec![],
+            },
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            replaying,
+            file: Mutex::new(file),
+            played: Mutex::default(),
+        })
+    }
+    fn file(&self) -> MutexGuard<'_, CassetteFile> {
+        lock(&self.file)
+    }
+    pub fn replaying(&self) -> bool {
+        self.replaying
+    }
+    pub fn address(&self) -> Option<String> {
+        self.file().address.clone()
+    }
+    // Requests with the same method, path and body are answered in the order they were recorded, the
+    // last answer repeating once they are used up. The query only decides between them, so requests
+    // with a timestamp in the query, like updated since, still replay.
+    pub fn replay(&self, request: &Request) -> Result<Response> {
+        let request = RecordedRequest::from(request);
+        let file = self.file();
+        let mut played = lock(&self.played);
+        let found = [true, false].into_iter().find_map(|same_query| {
+            let matching: Vec<usize> = file
+                .interactions
+                .iter()
+                .enumerate()
+                .filter(|(_, recorded)| {
+                    recorded.request.method == request.method
+                        && recorded.request.path == request.path
+                        && recorded.request.body == request.body
+                        && (!same_query || recorded.request.query == request.query)
+                })
+                .map(|(index, _)| index)
+                .collect();
+            matching
+                .iter()
+                .find(|index| !played.contains(*index))
+                .or(matching.last())
+                .copied()
+        });
+        let Some(index) = found else {
+            bail!(
+                "Cassette {} has no response for {} {}",
+                self.path.display(),
+                request.method,
+                request.path
+            );
+        };
+        played.insert(index);
+        let recorded = &file.interactions[index].response;
+        let mut response = http::Response::builder().status(recorded.status);
+        for (name, value) in &recorded.headers {
+            response = response.header(name, value);
+        }
+        let response = response
+            .body(body_bytes(&recorded.body)?)
+            .with_context(|| format!("Invalid response in cassette {}", self.path.display()))?;
+        Ok(Response::from(response))
+    }
+    pub async fn record(&self, request: RecordedRequest, response: Response) -> Result<Response> {
+        let (recorded, response) =
+            buffer_response(response, |status, headers, bytes| RecordedResponse {
+                status: status.as_u16(),
+                headers: headers
+                    .iter()
+                    .filter(|(name, _)| !SKIPPED_HEADERS.contains(name))
+                    .filter_map(|(name, value)| {
+                        Some((name.to_string(), value.to_str().ok()?.to_string()))
+                    })
+                    .collect(),
+                body: body_value(bytes),
+            })
+            .await?;
+        let mut file = self.file();
+        file.interactions.push(Interaction {
+            request,
+            response: recorded,
+        });
+        self.save(&file)?;
+        Ok(response)
+    }
+    fn save(&self, file: &CassetteFile) -> Result<()> {
+        write_atomic(&self.path, &serde_json::to_vec_pretty(file)?, false)
+            .with_context(|| format!("Failed to write cassette {}", self.path.display()))
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::{Method, StatusCode, Url};
+    use serde_json::json;
+    fn request(method: Method, url: &str) -> Request {
+        Request::new(method, Url::parse(url).unwrap())
+    }
+    fn interaction(path: &str, query: Option<&str>, body: Value) -> Interaction {
+        Interaction {
+            request: RecordedRequest {
+                method: "GET".to_string(),
+                path: path.to_string(),
+                query: query.map(str::to_string),
+                body: Value::Null,
+            },
+            response: RecordedResponse {
+                status: 200,
+                headers: BTreeMap::from([("etag".to_string(), "\"v1\"".to_string())]),
+                body,
+            },
+        }
+    }
+    fn cassette(dir: &Path, interactions: Vec<Interaction>) -> Cassette {
+        let path = dir.join("cassette.json");
+        let file = CassetteFile {
+            recorded_at: None,
+            address: Some("http://management".to_string()),
+            interactions,
+        };
+        std::fs::write(&path, serde_json::to_vec(&file).unwrap()).unwrap();
+        Cassette::open(&path, CassetteMode::Auto, None).unwrap()
+    }
+    async fn body(response: Response) -> Value {
+        serde_json::from_slice(&response.bytes().await.unwrap()).unwrap()
+    }
+    #[tokio::test]
+    async fn replays_in_order_and_repeats_the_last_answer() {
+        let dir = tempfile::tempdir().unwrap();
+        let cassette = cassette(
+            dir.path(),
+            vec![
+                interaction("/version", None, json!({"n": 1})),
+                interaction("/version", None, json!({"n": 2})),
+            ],
+        );
+        assert!(cassette.replaying());
+        assert_eq!(cassette.address().as_deref(), Some("http://management"));
+        let version = || request(Method::GET, "http://localhost/version");
+        let first = cassette.replay(&version()).unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(first.headers()["etag"], "\"v1\"");
+        assert_eq!(body(first).await, json!({"n": 1}));
+        assert_eq!(
+            body(cassette.replay(&version()).unwrap()).await,
+            json!({"n": 2})
+        );
+        assert_eq!(
+            body(cassette.replay(&version()).unwrap()).await,
+            json!({"n": 2})
+        );
+    }
+    #[tokio::test]
+    async fn prefers_the_same_query_but_replays_any() {
+        let dir = tempfile::tempdir().unwrap();
+        let cassette = cassette(
+            dir.path(),
+            vec![
+                interaction("/inventory/updated", Some("since=a"), json!(["a"])),
+                interaction("/inventory/updated", Some("since=b"), json!(["b"])),
+            ],
+        );
+        let updated = |query: &str| {
+            let url = format!("http://localhost/inventory/updated?{}", query);
+            cassette.replay(&request(Method::GET, &url)).unwrap()
+        };
+        assert_eq!(body(updated("since=b")).await, json!(["b"]));
+        assert_eq!(body(updated("since=c")).await, json!(["a"]));
+    }
+    #[test]
+    fn fails_for_requests_never_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let cassette = cassette(dir.path(), vec![interaction("/version", None, Value::Null)]);
+        let post = request(Method::POST, "http://localhost/version");
+        assert!(cassette.replay(&post).is_err());
+        let other = request(Method::GET, "http://localhost/registration");
+        assert!(cassette.replay(&other).is_err());
+    }
+}
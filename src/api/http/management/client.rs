@@ -1,19 +1,42 @@
+use crate::api::error::{DiciError, ErrorKind};
+use crate::api::http::management::api::ManagementApi;
+use crate::api::http::management::cache::{CachedResponse, Endpoint, ResponseCache};
+use crate::api::http::management::cassette::{Cassette, RecordedRequest};
 use crate::api::http::management::config::ManagementConfig;
 use crate::api::http::management::model::inventory::Inventory;
 use crate::api::http::management::model::registration::Registration;
-use crate::api::http::management::model::sync::IcebergLocationSync;
+use crate::api::http::management::model::sync::{
+    DomainIcebergAssetImpl, IcebergAssetImpl, IcebergLocationSync,
+};
 use crate::api::http::management::model::version::GitConfig;
-use anyhow::{Context, Result, anyhow};
+use crate::api::identifier::{Domain, FourByFour, IcebergLocation, RegistrationPath};
+use crate::api::retry::Retry;
+use anyhow::{Context, Result, anyhow, bail};
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use reqwest::{Client, StatusCode};
+use reqwest::header::AUTHORIZATION;
+use reqwest::{
+    Certificate, Client, Identity, Method, Request, RequestBuilder, Response, StatusCode, Url,
+};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 use typed_builder::TypedBuilder;
+const USER_AGENT: &str = concat!("diceberg/", env!("CARGO_PKG_VERSION"));
 #[derive(TypedBuilder, Clone)]
 pub struct ManagementClient {
-    #[builder(default)]
-    http_client: Client,
+    // Replaces the client built from the config, auth and retries still apply.
+    #[builder(default, setter(strip_option))]
+    http_client: Option<Client>,
     #[builder(default)]
     config: ManagementConfig,
+    #[builder(default, setter(skip))]
+    shared: Arc<OnceLock<Client>>,
+    #[builder(default, setter(skip))]
+    cassette: Arc<OnceLock<Option<Cassette>>>,
+    #[builder(default, setter(skip))]
+    cache: Arc<OnceLock<Option<ResponseCache>>>,
 }
 impl Default for ManagementClient {
     fn default() -> Self {
@@ -21,43 +44,286 @@ impl Default for ManagementClient {
     }
 }
 impl ManagementClient {
-    pub async fn fetch_inventories(&self) -> Result<Vec<Inventory>> {
-        self.http_client
-            .get(format!("{}/inventory", self.config.address))
-            .send()
-            .await
-            .context("Request to dici management failed")?
-            .json::<Vec<Inventory>>()
+    // The management address with the given path segments appended, each one percent-encoded.
+    // Replaying a cassette needs no address, the recorded one is used.
+    fn url<'a>(&self, segments: impl IntoIterator<Item = &'a str>) -> Result<Url> {
+        let address = match self.config.address() {
+            Ok(address) => address,
+            Err(e) => self
+                .cassette()?
+                .filter(|cassette| cassette.replaying())
+                .and_then(Cassette::address)
+                .ok_or(e)?,
+        };
+        let mut url = Url::parse(&address)
+            .with_context(|| format!("Invalid dici management address {}", address))?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow!("Invalid dici management address {}", address))?
+            .pop_if_empty()
+            .extend(segments);
+        Ok(url)
+    }
+    // Built on first use and shared between clones so connections are pooled.
+    fn http_client(&self) -> Result<&Client> {
+        if let Some(client) = &self.http_client {
+            return Ok(client);
+        }
+        if let Some(client) = self.shared.get() {
+            return Ok(client);
+        }
+        let options = self.config.options()?;
+        let mut builder = Client::builder()
+            .user_agent(USER_AGENT)
+            .connect_timeout(options.connect_timeout())
+            .timeout(options.request_timeout());
+        if let Some(path) = &options.ca_cert {
+            let pem = std::fs::read(path)
+                .with_context(|| format!("Failed to read CA certificate {}", path.display()))?;
+            builder = builder.add_root_certificate(
+                Certificate::from_pem(&pem)
+                    .with_context(|| format!("Invalid CA certificate {}", path.display()))?,
+            );
+        }
+        match (&options.client_cert, &options.client_key) {
+            (Some(cert), Some(key)) => {
+                let cert_pem = std::fs::read(cert).with_context(|| {
+                    format!("Failed to read client certificate {}", cert.display())
+                })?;
+                let key_pem = std::fs::read(key)
+                    .with_context(|| format!("Failed to read client key {}", key.display()))?;
+                builder = builder.identity(
+                    Identity::from_pkcs8_pem(&cert_pem, &key_pem)
+                        .context("Invalid client certificate or key")?,
+                );
+            }
+            (None, None) => {}
+            _ => bail!("Both client_cert and client_key are needed for a client certificate"),
+        }
+        let client = builder
+            .build()
+            .context("Failed to construct dici management http client")?;
+        Ok(self.shared.get_or_init(|| client))
+    }
+    // Opened on first use and shared between clones, so one cassette holds all their traffic.
+    fn cassette(&self) -> Result<Option<&Cassette>> {
+        if let Some(cassette) = self.cassette.get() {
+            return Ok(cassette.as_ref());
+        }
+        let options = self.config.options()?;
+        let cassette = options
+            .cassette
+            .as_deref()
+            .map(|path| {
+                Cassette::open(
+                    path,
+                    options.cassette_mode.unwrap_or_default(),
+                    self.config.address().ok(),
+                )
+            })
+            .transpose()?;
+        Ok(self.cassette.get_or_init(|| cassette).as_ref())
+    }
+    // Built on first use and shared between clones, like the http client.
+    fn cache(&self) -> Result<Option<&ResponseCache>> {
+        if let Some(cache) = self.cache.get() {
+            return Ok(cache.as_ref());
+        }
+        let options = self.config.options()?;
+        let cache = options.cache.and_then(|mode| {
+            ResponseCache::new(mode, options.cache_dir.clone(), &options.cache_ttl_seconds)
+        });
+        Ok(self.cache.get_or_init(|| cache).as_ref())
+    }
+    // Classified by the path below the management address, which may have a path of its own.
+    fn endpoint(&self, request: &Request) -> Result<Endpoint> {
+        let address = Url::parse(&self.config.address()?)?;
+        let prefix = address
+            .path_segments()
+            .map(|segments| segments.filter(|segment| !segment.is_empty()).count())
+            .unwrap_or_default();
+        let segments = request.url().path_segments().into_iter().flatten();
+        Ok(Endpoint::of(request, segments.skip(prefix)))
+    }
+    // Cached responses belong to the credentials they were fetched with, so profiles sharing a cache
+    // never answer each other's lookups. Only a hash of them ends up in the cache.
+    fn identity(&self, request: &Request) -> Result<String> {
+        let options = self.config.options()?;
+        let mut hasher = Sha256::new();
+        if let Some(authorization) = request.headers().get(AUTHORIZATION) {
+            hasher.update(authorization.as_bytes());
+        }
+        hasher.update([0]);
+        if let Some(client_cert) = &options.client_cert {
+            hasher.update(client_cert.to_string_lossy().as_bytes());
+        }
+        let hash = hasher.finalize();
+        Ok(hash[..8]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect())
+    }
+    fn request(&self, method: Method, url: Url) -> Result<RequestBuilder> {
+        let options = self.config.options()?;
+        let request = self.http_client()?.request(method, url);
+        Ok(match (options.token, options.username) {
+            (Some(token), _) => request.bearer_auth(token),
+            (None, Some(username)) => request.basic_auth(username, options.password),
+            (None, None) => request,
+        })
+    }
+    // Answered from the cassette when replaying, the response after any retries is recorded otherwise.
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let (client, request) = request.build_split();
+        let request = request?;
+        match self.cassette()? {
+            Some(cassette) if cassette.replaying() => cassette.replay(&request),
+            Some(cassette) => {
+                let recorded = RecordedRequest::from(&request);
+                let response = self.fetch(&client, request).await?;
+                cassette.record(recorded, response).await
+            }
+            None => self.fetch(&client, request).await,
+        }
+    }
+    // Lookups are answered from the cache while fresh, and revalidated with their ETag or
+    // Last-Modified once stale. Syncs clear the cache, the lookups cached before may be stale.
+    async fn fetch(&self, client: &Client, mut request: Request) -> Result<Response> {
+        let Some(cache) = self.cache()? else {
+            return self.execute(client, request).await;
+        };
+        let ttl = match self.endpoint(&request)? {
+            Endpoint::Lookup(endpoint) => cache.ttl(endpoint),
+            Endpoint::Sync => {
+                let response = self.execute(client, request).await;
+                cache.clear();
+                return response;
+            }
+            Endpoint::Other => Duration::ZERO,
+        };
+        if ttl.is_zero() {
+            return self.execute(client, request).await;
+        }
+        let key = format!("{}@{}", self.identity(&request)?, request.url());
+        let cached = cache.get(&key);
+        if let Some(cached) = &cached {
+            if cached.fresh(ttl) {
+                return cached.response();
+            }
+            cached.revalidate(&mut request);
+        }
+        let response = self.execute(client, request).await?;
+        match (response.status(), cached) {
+            (StatusCode::NOT_MODIFIED, Some(cached)) => {
+                let cached = cached.refreshed();
+                let response = cached.response();
+                cache.put(cached);
+                response
+            }
+            (StatusCode::OK, _) => {
+                let (cached, response) = CachedResponse::store(&key, response).await?;
+                cache.put(cached);
+                Ok(response)
+            }
+            _ => Ok(response),
+        }
+    }
+    // GETs are retried on connection errors and 5xx responses, waiting `retry_backoff` and doubling it each time.
+    // Syncs never are, GET /sync/iceberg/{location} changes dici management and may still be running.
+    async fn execute(&self, client: &Client, request: Request) -> Result<Response> {
+        let options = self.config.options()?;
+        let retryable =
+            request.method() == Method::GET && self.endpoint(&request)? != Endpoint::Sync;
+        let retries = match retryable {
+            true => options.retries(),
+            false => 0,
+        };
+        // A streamed body can only be sent once.
+        if request.try_clone().is_none() {
+            return Ok(client.execute(request).await?);
+        }
+        let request = &request;
+        let (_, result) = Retry::new(retries, options.retry_backoff())
+            .run(
+                move || async move {
+                    let attempt = request
+                        .try_clone()
+                        .context("Failed to copy the dici management request")?;
+                    Ok(client.execute(attempt).await?)
+                },
+                |result: &Result<Response>| match result {
+                    Ok(response) => response.status().is_server_error(),
+                    Err(e) => ErrorKind::of(e).transient(),
+                },
+            )
+            .await;
+        result
+    }
+    // Keeps the response body, it usually says why dici management refused the request.
+    async fn status_error(response: Response) -> anyhow::Error {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => DiciError::Unauthorized {
+                status: status.as_u16(),
+                body,
+            },
+            StatusCode::REQUEST_TIMEOUT | StatusCode::GATEWAY_TIMEOUT => {
+                DiciError::Timeout(format!("Dici management timed out ({}): {}", status, body))
+            }
+            status if status.is_server_error() => DiciError::Unavailable {
+                status: status.as_u16(),
+                body,
+            },
+            _ => DiciError::ManagementStatus {
+                status: status.as_u16(),
+                body,
+            },
+        }
+        .into()
+    }
+}
+#[async_trait]
+impl ManagementApi for ManagementClient {
+    async fn fetch_inventories(&self) -> Result<Vec<Inventory>> {
+        let response = self
+            .send(self.request(Method::GET, self.url(["inventory"])?)?)
             .await
-            .context("Deserializing dici management response failed")
+            .context("Request to dici management failed")?;
+        match response.status() {
+            status if status.is_success() => response
+                .json::<Vec<Inventory>>()
+                .await
+                .context("Deserializing dici management response failed"),
+            _ => Err(Self::status_error(response).await),
+        }
     }
-    pub async fn fetch_inventory_by_fxf(&self, fxf: String) -> Result<Inventory> {
+    async fn fetch_inventory_by_fxf(&self, fxf: &FourByFour) -> Result<Inventory> {
         let response = self
-            .http_client
-            .get(format!("{}/inventory/fxf/{}", self.config.address, fxf))
-            .send()
+            .send(self.request(Method::GET, self.url(["inventory", "fxf", fxf.as_str()])?)?)
             .await
             .context("Request to dici management failed")?;
         match response.status() {
-            StatusCode::NOT_FOUND => Err(anyhow!("Inventory not found")),
+            StatusCode::NOT_FOUND => Err(DiciError::NotFound(format!(
+                "Inventory not found for fourByFour {}",
+                fxf
+            ))
+            .into()),
             status if status.is_success() => response
                 .json::<Inventory>()
                 .await
                 .context("Deserializing dici management response failed"),
-            status => Err(anyhow!("Unexpected status code: {}", status)),
+            _ => Err(Self::status_error(response).await),
         }
     }
-    pub async fn fetch_inventories_by_iceberg_location(
+    async fn fetch_inventories_by_iceberg_location(
         &self,
-        iceberg_location: String,
+        iceberg_location: &IcebergLocation,
     ) -> Result<Vec<Inventory>> {
         let response = self
-            .http_client
-            .get(format!(
-                "{}/inventory/iceberg/{}",
-                self.config.address, iceberg_location
-            ))
-            .send()
+            .send(self.request(
+                Method::GET,
+                self.url(["inventory", "iceberg", iceberg_location.as_str()])?,
+            )?)
             .await
             .context("Request to dici management failed")?;
         match response.status() {
@@ -66,24 +332,31 @@ impl ManagementClient {
                 .json::<Vec<Inventory>>()
                 .await
                 .context("Deserializing dici management response failed"),
-            status => Err(anyhow!("Unexpected status code: {}", status)),
+            _ => Err(Self::status_error(response).await),
         }
     }
-    pub async fn fetch_registrations(&self) -> Result<Vec<Registration>> {
-        self.http_client
-            .get(format!("{}/registration", self.config.address))
-            .send()
-            .await
-            .context("Request to dici management failed")?
-            .json::<Vec<Registration>>()
+    async fn fetch_registrations(&self) -> Result<Vec<Registration>> {
+        let response = self
+            .send(self.request(Method::GET, self.url(["registration"])?)?)
             .await
-            .context("Deserializing dici management response failed")
+            .context("Request to dici management failed")?;
+        match response.status() {
+            status if status.is_success() => response
+                .json::<Vec<Registration>>()
+                .await
+                .context("Deserializing dici management response failed"),
+            _ => Err(Self::status_error(response).await),
+        }
     }
-    pub async fn fetch_registrations_by_path(&self, path: String) -> Result<Vec<Registration>> {
+    async fn fetch_registrations_by_path(
+        &self,
+        path: &RegistrationPath,
+    ) -> Result<Vec<Registration>> {
         let response = self
-            .http_client
-            .get(format!("{}/query/{}", self.config.address, path))
-            .send()
+            .send(self.request(
+                Method::GET,
+                self.url(["query"].into_iter().chain(path.segments()))?,
+            )?)
             .await
             .context("Request to dici management failed")?;
         match response.status() {
@@ -92,19 +365,22 @@ impl ManagementClient {
                 .json::<Vec<Registration>>()
                 .await
                 .context("Deserializing dici management response failed"),
-            status => Err(anyhow!("Unexpected status code: {}", status)),
+            _ => Err(Self::status_error(response).await),
         }
     }
-    pub async fn fetch_registrations_by_path_and_metadata(
+    async fn fetch_registrations_by_path_and_metadata(
         &self,
-        path: String,
-        metadata: HashMap<String, String>,
+        path: &RegistrationPath,
+        metadata: &HashMap<String, String>,
     ) -> Result<Vec<Registration>> {
         let response = self
-            .http_client
-            .post(format!("{}/query/{}", self.config.address, path))
-            .json(&metadata)
-            .send()
+            .send(
+                self.request(
+                    Method::POST,
+                    self.url(["query"].into_iter().chain(path.segments()))?,
+                )?
+                .json(metadata),
+            )
             .await
             .context("Request to dici management failed")?;
         match response.status() {
@@ -113,39 +389,39 @@ impl ManagementClient {
                 .json::<Vec<Registration>>()
                 .await
                 .context("Deserializing dici management response failed"),
-            status => Err(anyhow!("Unexpected status code: {}", status)),
+            _ => Err(Self::status_error(response).await),
         }
     }
-    pub async fn fetch_registration_by_iceberg_location(
+    async fn fetch_registration_by_iceberg_location(
         &self,
-        iceberg_location: String,
+        iceberg_location: &IcebergLocation,
     ) -> Result<Registration> {
         let response = self
-            .http_client
-            .get(format!(
-                "{}/registration/iceberg/{}",
-                self.config.address, iceberg_location
-            ))
-            .send()
+            .send(self.request(
+                Method::GET,
+                self.url(["registration", "iceberg", iceberg_location.as_str()])?,
+            )?)
             .await
             .context("Request to dici management failed")?;
         match response.status() {
-            StatusCode::NOT_FOUND => Err(anyhow!("Registration not found")),
+            StatusCode::NOT_FOUND => Err(DiciError::NotFound(format!(
+                "Registration not found for icebergLocation {}",
+                iceberg_location
+            ))
+            .into()),
             status if status.is_success() => response
                 .json::<Registration>()
                 .await
                 .context("Deserializing dici management response failed"),
-            status => Err(anyhow!("Unexpected status code: {}", status)),
+            _ => Err(Self::status_error(response).await),
         }
     }
-    pub async fn fetch_inventories_by_domain(&self, domain: String) -> Result<Vec<Inventory>> {
+    async fn fetch_inventories_by_domain(&self, domain: &Domain) -> Result<Vec<Inventory>> {
         let response = self
-            .http_client
-            .get(format!(
-                "{}/inventory/domain/{}",
-                self.config.address, domain
-            ))
-            .send()
+            .send(self.request(
+                Method::GET,
+                self.url(["inventory", "domain", domain.as_str()])?,
+            )?)
             .await
             .context("Request to dici management failed")?;
         match response.status() {
@@ -154,34 +430,34 @@ impl ManagementClient {
                 .json::<Vec<Inventory>>()
                 .await
                 .context("Deserializing dici management response failed"),
-            status => Err(anyhow!("Unexpected status code: {}", status)),
+            _ => Err(Self::status_error(response).await),
         }
     }
-    pub async fn fetch_version(&self) -> Result<GitConfig> {
+    async fn fetch_version(&self) -> Result<GitConfig> {
         let response = self
-            .http_client
-            .get(format!("{}/version", self.config.address))
-            .send()
+            .send(self.request(Method::GET, self.url(["version"])?)?)
             .await
             .context("Request to dici management /version failed")?;
         match response.status() {
-            StatusCode::NOT_FOUND => Err(anyhow!("Version information not found")),
+            StatusCode::NOT_FOUND => {
+                Err(DiciError::NotFound("Version information not found".to_string()).into())
+            }
             status if status.is_success() => response
                 .json::<GitConfig>()
                 .await
                 .context("Deserializing /version response into GitConfig failed"),
-            status => Err(anyhow!("Unexpected status code: {}", status)),
+            _ => Err(Self::status_error(response).await),
         }
     }
-    pub async fn fetch_inventories_updated_since(
+    async fn fetch_inventories_updated_since(
         &self,
         since: DateTime<Utc>,
     ) -> Result<Vec<Inventory>> {
         let response = self
-            .http_client
-            .get(format!("{}/inventory/updated", self.config.address))
-            .query(&[("since", since.to_rfc3339())])
-            .send()
+            .send(
+                self.request(Method::GET, self.url(["inventory", "updated"])?)?
+                    .query(&[("since", since.to_rfc3339())]),
+            )
             .await
             .context("Request to dici management failed")?;
         match response.status() {
@@ -190,21 +466,24 @@ impl ManagementClient {
                 .json::<Vec<Inventory>>()
                 .await
                 .context("Deserializing dici management response failed"),
-            status => Err(anyhow!("Unexpected status code: {}", status)),
+            _ => Err(Self::status_error(response).await),
         }
     }
-    pub async fn fetch_inventories_by_iceberg_location_and_table(
+    async fn fetch_inventories_by_iceberg_location_and_table(
         &self,
-        iceberg_location: String,
-        schema_table: String,
+        iceberg_location: &IcebergLocation,
+        schema_table: &str,
     ) -> Result<Vec<Inventory>> {
         let response = self
-            .http_client
-            .get(format!(
-                "{}/inventory/iceberg/{}/{}",
-                self.config.address, iceberg_location, schema_table
-            ))
-            .send()
+            .send(self.request(
+                Method::GET,
+                self.url([
+                    "inventory",
+                    "iceberg",
+                    iceberg_location.as_str(),
+                    schema_table,
+                ])?,
+            )?)
             .await
             .context("Request to dici management failed")?;
         match response.status() {
@@ -213,52 +492,52 @@ impl ManagementClient {
                 .json::<Vec<Inventory>>()
                 .await
                 .context("Deserializing dici management response failed"),
-            status => Err(anyhow!("Unexpected status code: {}", status)),
+            _ => Err(Self::status_error(response).await),
         }
     }
-    pub async fn fetch_inventory_by_id(
+    async fn fetch_inventory_by_id(
         &self,
-        domain: String,
-        iceberg_location: String,
-        schema_table: String,
+        domain: &Domain,
+        iceberg_location: &IcebergLocation,
+        schema_table: &str,
     ) -> Result<Inventory> {
         let response = self
-            .http_client
-            .get(format!(
-                "{}/inventory/id/{}/{}/{}",
-                self.config.address, domain, iceberg_location, schema_table
-            ))
-            .send()
+            .send(self.request(
+                Method::GET,
+                self.url([
+                    "inventory",
+                    "id",
+                    domain.as_str(),
+                    iceberg_location.as_str(),
+                    schema_table,
+                ])?,
+            )?)
             .await
             .context("Request to dici management failed")?;
         match response.status() {
-            StatusCode::NOT_FOUND => Err(anyhow!(
+            StatusCode::NOT_FOUND => Err(DiciError::NotFound(format!(
                 "Inventory not found for domain={}, location={}, table={}",
-                domain,
-                iceberg_location,
-                schema_table
-            )),
+                domain, iceberg_location, schema_table
+            ))
+            .into()),
             status if status.is_success() => response
                 .json::<Inventory>()
                 .await
                 .context("Deserializing dici management response failed"),
-            status => Err(anyhow!("Unexpected status code: {}", status)),
+            _ => Err(Self::status_error(response).await),
         }
     }
-    pub async fn sync_table(
+    async fn sync_table(
         &self,
-        iceberg_location: String,
-        schema_table: String,
+        iceberg_location: &IcebergLocation,
+        schema_table: &str,
     ) -> Result<Vec<Inventory>> {
-        let body = serde_json::json!({
-            "icebergLocation": iceberg_location,
-            "schemaTable": schema_table,
-        });
+        let body = IcebergAssetImpl {
+            iceberg_location: iceberg_location.clone(),
+            schema_table: schema_table.to_string(),
+        };
         let response = self
-            .http_client
-            .post(format!("{}/sync", self.config.address))
-            .json(&body)
-            .send()
+            .send(self.request(Method::POST, self.url(["sync"])?)?.json(&body))
             .await
             .context("Request to /sync failed")?;
         match response.status() {
@@ -267,56 +546,62 @@ impl ManagementClient {
                 .json::<Vec<Inventory>>()
                 .await
                 .context("Deserializing /sync response failed"),
-            status => Err(anyhow!("Unexpected status code: {}", status)),
+            _ => Err(Self::status_error(response).await),
         }
     }
-    pub async fn sync_table_domain(
+    async fn sync_table_domain(
         &self,
-        domain: String,
-        iceberg_location: String,
-        schema_table: String,
+        domain: &Domain,
+        iceberg_location: &IcebergLocation,
+        schema_table: &str,
     ) -> Result<Inventory> {
-        let body = serde_json::json!({
-            "domain": domain,
-            "icebergLocation": iceberg_location,
-            "schemaTable": schema_table,
-        });
+        let body = DomainIcebergAssetImpl {
+            domain: domain.clone(),
+            iceberg_location: iceberg_location.clone(),
+            schema_table: schema_table.to_string(),
+        };
         let response = self
-            .http_client
-            .post(format!("{}/sync/domain", self.config.address))
-            .json(&body)
-            .send()
+            .send(
+                self.request(Method::POST, self.url(["sync", "domain"])?)?
+                    .json(&body),
+            )
             .await
             .context("Request to /sync/domain failed")?;
         match response.status() {
-            StatusCode::NOT_FOUND => Err(anyhow!("Inventory not found for domain sync")),
+            StatusCode::NOT_FOUND => Err(DiciError::NotFound(format!(
+                "Inventory not found for domain sync of {}/{}/{}",
+                domain, iceberg_location, schema_table
+            ))
+            .into()),
             status if status.is_success() => response
                 .json::<Inventory>()
                 .await
                 .context("Deserializing /sync/domain response failed"),
-            status => Err(anyhow!("Unexpected status code: {}", status)),
+            _ => Err(Self::status_error(response).await),
         }
     }
-    pub async fn sync_iceberg_location(
+    async fn sync_iceberg_location(
         &self,
-        iceberg_location: String,
+        iceberg_location: &IcebergLocation,
     ) -> Result<IcebergLocationSync> {
         let response = self
-            .http_client
-            .get(format!(
-                "{}/sync/iceberg/{}",
-                self.config.address, iceberg_location
-            ))
-            .send()
+            .send(self.request(
+                Method::GET,
+                self.url(["sync", "iceberg", iceberg_location.as_str()])?,
+            )?)
             .await
             .context("Request to /sync/iceberg/{location} failed")?;
         match response.status() {
-            StatusCode::NOT_FOUND => Err(anyhow!("Iceberg location not found")),
+            StatusCode::NOT_FOUND => Err(DiciError::NotFound(format!(
+                "Iceberg location {} not found",
+                iceberg_location
+            ))
+            .into()),
             status if status.is_success() => response
                 .json::<IcebergLocationSync>()
                 .await
                 .context("Deserializing /sync/iceberg response failed"),
-            status => Err(anyhow!("Unexpected status code: {}", status)),
+            _ => Err(Self::status_error(response).await),
         }
     }
 }
@@ -1,22 +1,190 @@
-use anyhow::Context;
+use crate::api::config::error::ConfigError;
+use crate::api::http::management::cache::{CacheMode, parse_ttls};
+use crate::api::http::management::cassette::CassetteMode;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
+use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
 use typed_builder::TypedBuilder;
-fn management_address_from_env() -> anyhow::Result<String> {
-    env::var("DICI_MANAGEMENT_ADDRESS").context("DICI_MANAGEMENT_ADDRESS is not set")
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+const DEFAULT_RETRIES: u32 = 3;
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(250);
+pub(crate) fn management_address_from_env() -> Option<ManagementAddress> {
+    env::var("DICI_MANAGEMENT_ADDRESS").ok()
 }
-fn default_management_address() -> String {
-    management_address_from_env()
-        .context("Could not determine management address")
-        .unwrap()
+pub(crate) fn management_snapshot_from_env() -> Option<PathBuf> {
+    env::var("DICI_MANAGEMENT_SNAPSHOT").ok().map(PathBuf::from)
+}
+fn parse_env<T: FromStr>(name: &'static str) -> Result<Option<T>, ConfigError>
+where
+    T::Err: std::fmt::Display,
+{
+    env::var(name)
+        .ok()
+        .map(|value| {
+            value.parse::<T>().map_err(|e| ConfigError::Invalid {
+                setting: name,
+                reason: e.to_string(),
+            })
+        })
+        .transpose()
+}
+fn cache_ttls_from_env() -> Result<BTreeMap<String, u64>, ConfigError> {
+    match env::var("DICI_MANAGEMENT_CACHE_TTL_SECONDS") {
+        Ok(value) => parse_ttls(&value).map_err(|reason| ConfigError::Invalid {
+            setting: "DICI_MANAGEMENT_CACHE_TTL_SECONDS",
+            reason,
+        }),
+        Err(_) => Ok(BTreeMap::new()),
+    }
+}
+pub(crate) fn management_options_from_env() -> Result<ManagementOptions, ConfigError> {
+    Ok(ManagementOptions {
+        connect_timeout_seconds: parse_env("DICI_MANAGEMENT_CONNECT_TIMEOUT_SECONDS")?,
+        request_timeout_seconds: parse_env("DICI_MANAGEMENT_REQUEST_TIMEOUT_SECONDS")?,
+        retries: parse_env("DICI_MANAGEMENT_RETRIES")?,
+        retry_backoff_ms: parse_env("DICI_MANAGEMENT_RETRY_BACKOFF_MS")?,
+        token: env::var("DICI_MANAGEMENT_TOKEN").ok(),
+        username: env::var("DICI_MANAGEMENT_USERNAME").ok(),
+        password: env::var("DICI_MANAGEMENT_PASSWORD").ok(),
+        ca_cert: env::var("DICI_MANAGEMENT_CA_CERT").ok().map(PathBuf::from),
+        client_cert: env::var("DICI_MANAGEMENT_CLIENT_CERT")
+            .ok()
+            .map(PathBuf::from),
+        client_key: env::var("DICI_MANAGEMENT_CLIENT_KEY")
+            .ok()
+            .map(PathBuf::from),
+        cassette: env::var("DICI_MANAGEMENT_CASSETTE").ok().map(PathBuf::from),
+        cassette_mode: parse_env("DICI_MANAGEMENT_CASSETTE_MODE")?,
+        cache: parse_env("DICI_MANAGEMENT_CACHE")?,
+        cache_dir: env::var("DICI_MANAGEMENT_CACHE_DIR")
+            .ok()
+            .map(PathBuf::from),
+        cache_ttl_seconds: cache_ttls_from_env()?,
+    })
 }
 pub type ManagementAddress = String;
-#[derive(TypedBuilder, Clone)]
+// How requests to dici management are made, the `management` table of a config profile.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ManagementOptions {
+    pub connect_timeout_seconds: Option<u64>,
+    pub request_timeout_seconds: Option<u64>,
+    // Times a GET is retried on connection errors and 5xx responses.
+    pub retries: Option<u32>,
+    pub retry_backoff_ms: Option<u64>,
+    // Sent as a bearer token, takes precedence over username and password.
+    pub token: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    // PEM files, the client key must be PKCS#8.
+    pub ca_cert: Option<PathBuf>,
+    pub client_cert: Option<PathBuf>,
+    pub client_key: Option<PathBuf>,
+    // Records dici management traffic to this file, or replays it without the network.
+    pub cassette: Option<PathBuf>,
+    pub cassette_mode: Option<CassetteMode>,
+    // Caches lookups. The cli defaults to disk, shared between runs, and the mcp server to memory.
+    pub cache: Option<CacheMode>,
+    pub cache_dir: Option<PathBuf>,
+    // Per endpoint: inventories, inventories_updated, registrations and version.
+    #[serde(default)]
+    pub cache_ttl_seconds: BTreeMap<String, u64>,
+}
+// Credentials are left out so options can be logged.
+impl Debug for ManagementOptions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManagementOptions")
+            .field("connect_timeout_seconds", &self.connect_timeout_seconds)
+            .field("request_timeout_seconds", &self.request_timeout_seconds)
+            .field("retries", &self.retries)
+            .field("retry_backoff_ms", &self.retry_backoff_ms)
+            .field("token", &self.token.as_ref().map(|_| "***"))
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "***"))
+            .field("ca_cert", &self.ca_cert)
+            .field("client_cert", &self.client_cert)
+            .field("client_key", &self.client_key)
+            .field("cassette", &self.cassette)
+            .field("cassette_mode", &self.cassette_mode)
+            .field("cache", &self.cache)
+            .field("cache_dir", &self.cache_dir)
+            .field("cache_ttl_seconds", &self.cache_ttl_seconds)
+            .finish()
+    }
+}
+impl ManagementOptions {
+    pub fn merge(self, other: ManagementOptions) -> ManagementOptions {
+        let mut cache_ttl_seconds = self.cache_ttl_seconds;
+        cache_ttl_seconds.extend(other.cache_ttl_seconds);
+        ManagementOptions {
+            connect_timeout_seconds: other
+                .connect_timeout_seconds
+                .or(self.connect_timeout_seconds),
+            request_timeout_seconds: other
+                .request_timeout_seconds
+                .or(self.request_timeout_seconds),
+            retries: other.retries.or(self.retries),
+            retry_backoff_ms: other.retry_backoff_ms.or(self.retry_backoff_ms),
+            token: other.token.or(self.token),
+            username: other.username.or(self.username),
+            password: other.password.or(self.password),
+            ca_cert: other.ca_cert.or(self.ca_cert),
+            client_cert: other.client_cert.or(self.client_cert),
+            client_key: other.client_key.or(self.client_key),
+            cassette: other.cassette.or(self.cassette),
+            cassette_mode: other.cassette_mode.or(self.cassette_mode),
+            cache: other.cache.or(self.cache),
+            cache_dir: other.cache_dir.or(self.cache_dir),
+            cache_ttl_seconds,
+        }
+    }
+    pub fn connect_timeout(&self) -> Duration {
+        self.connect_timeout_seconds
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_CONNECT_TIMEOUT)
+    }
+    pub fn request_timeout(&self) -> Duration {
+        self.request_timeout_seconds
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_REQUEST_TIMEOUT)
+    }
+    pub fn retries(&self) -> u32 {
+        self.retries.unwrap_or(DEFAULT_RETRIES)
+    }
+    pub fn retry_backoff(&self) -> Duration {
+        self.retry_backoff_ms
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_RETRY_BACKOFF)
+    }
+}
+// Unset values fall back to the environment when a request is made.
+#[derive(TypedBuilder, Clone, Default)]
 pub struct ManagementConfig {
-    #[builder(default = default_management_address(), setter(into))]
-    pub address: ManagementAddress,
+    #[builder(default, setter(into, strip_option(fallback = address_opt)))]
+    pub address: Option<ManagementAddress>,
+    #[builder(default)]
+    pub options: ManagementOptions,
+    // An exported snapshot to answer from instead of dici management.
+    #[builder(default, setter(into, strip_option(fallback = snapshot_opt)))]
+    pub snapshot: Option<PathBuf>,
 }
-impl Default for ManagementConfig {
-    fn default() -> Self {
-        Self::builder().build()
+impl ManagementConfig {
+    pub fn address(&self) -> Result<ManagementAddress, ConfigError> {
+        self.address
+            .clone()
+            .or_else(management_address_from_env)
+            .ok_or(ConfigError::Missing {
+                setting: "Management address",
+                env: "DICI_MANAGEMENT_ADDRESS",
+                flag: "management-address",
+                key: "management_address",
+            })
+    }
+    pub fn options(&self) -> Result<ManagementOptions, ConfigError> {
+        Ok(management_options_from_env()?.merge(self.options.clone()))
     }
 }
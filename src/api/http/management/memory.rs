@@ -0,0 +1,253 @@
+use crate::api::error::DiciError;
+use crate::api::http::management::api::ManagementApi;
+use crate::api::http::management::model::inventory::Inventory;
+use crate::api::http::management::model::registration::Registration;
+use crate::api::http::management::model::snapshot::ManagementSnapshot;
+use crate::api::http::management::model::sync::IcebergLocationSync;
+use crate::api::http::management::model::version::GitConfig;
+use crate::api::identifier::{Domain, FourByFour, IcebergLocation, RegistrationPath};
+use crate::api::lock;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+fn in_table(inventory: &Inventory, iceberg_location: &IcebergLocation, schema_table: &str) -> bool {
+    &inventory.id.iceberg_location.iceberg_location == iceberg_location
+        && inventory.id.schema_table.schema_table == schema_table
+}
+// Like dici management, a path matches the registrations at or below it.
+fn below(registration: &Registration, path: &RegistrationPath) -> bool {
+    let mut segments = registration.id.path.segments();
+    path.segments()
+        .all(|segment| segments.next() == Some(segment))
+}
+// Answers from a snapshot held in memory, for tests and running without dici management. Syncs only
+// bump updatedAt of the inventories they name, clones share the same state.
+#[derive(Clone, Default)]
+pub struct InMemoryManagement {
+    snapshot: Arc<RwLock<ManagementSnapshot>>,
+}
+impl From<ManagementSnapshot> for InMemoryManagement {
+    fn from(snapshot: ManagementSnapshot) -> Self {
+        Self {
+            snapshot: Arc::new(RwLock::new(snapshot)),
+        }
+    }
+}
+impl InMemoryManagement {
+    pub fn new(inventories: Vec<Inventory>, registrations: Vec<Registration>) -> Self {
+        ManagementSnapshot {
+            inventories,
+            registrations,
+            ..ManagementSnapshot::default()
+        }
+        .into()
+    }
+    fn read(&self) -> RwLockReadGuard<'_, ManagementSnapshot> {
+        lock::read(&self.snapshot)
+    }
+    fn write(&self) -> RwLockWriteGuard<'_, ManagementSnapshot> {
+        lock::write(&self.snapshot)
+    }
+    pub fn snapshot(&self) -> ManagementSnapshot {
+        self.read().clone()
+    }
+    // Replaces the inventory with the same id.
+    pub fn insert_inventory(&self, inventory: Inventory) {
+        let mut snapshot = self.write();
+        snapshot.inventories.retain(|existing| {
+            existing.id.domain.domain != inventory.id.domain.domain
+                || !in_table(
+                    existing,
+                    &inventory.id.iceberg_location.iceberg_location,
+                    &inventory.id.schema_table.schema_table,
+                )
+        });
+        snapshot.inventories.push(inventory);
+    }
+    // Replaces the registration with the same path, see Registration::key.
+    pub fn insert_registration(&self, registration: Registration) {
+        let mut snapshot = self.write();
+        snapshot
+            .registrations
+            .retain(|existing| existing.key() != registration.key());
+        snapshot.registrations.push(registration);
+    }
+    fn inventories(&self, filter: impl Fn(&Inventory) -> bool) -> Vec<Inventory> {
+        self.read()
+            .inventories
+            .iter()
+            .filter(|inventory| filter(inventory))
+            .cloned()
+            .collect()
+    }
+    fn registrations(&self, filter: impl Fn(&Registration) -> bool) -> Vec<Registration> {
+        self.read()
+            .registrations
+            .iter()
+            .filter(|registration| filter(registration))
+            .cloned()
+            .collect()
+    }
+    fn touch(&self, filter: impl Fn(&Inventory) -> bool) -> Vec<Inventory> {
+        let now = Utc::now();
+        let mut snapshot = self.write();
+        snapshot
+            .inventories
+            .iter_mut()
+            .filter(|inventory| filter(inventory))
+            .map(|inventory| {
+                inventory.updated_at = now;
+                inventory.clone()
+            })
+            .collect()
+    }
+}
+#[async_trait]
+impl ManagementApi for InMemoryManagement {
+    async fn fetch_inventories(&self) -> Result<Vec<Inventory>> {
+        Ok(self.inventories(|_| true))
+    }
+    async fn fetch_inventory_by_fxf(&self, fxf: &FourByFour) -> Result<Inventory> {
+        self.inventories(|inventory| &inventory.four_by_four.four_by_four == fxf)
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                DiciError::NotFound(format!("Inventory not found for fourByFour {}", fxf)).into()
+            })
+    }
+    async fn fetch_inventories_by_iceberg_location(
+        &self,
+        iceberg_location: &IcebergLocation,
+    ) -> Result<Vec<Inventory>> {
+        Ok(self.inventories(|inventory| {
+            &inventory.id.iceberg_location.iceberg_location == iceberg_location
+        }))
+    }
+    async fn fetch_registrations(&self) -> Result<Vec<Registration>> {
+        Ok(self.registrations(|_| true))
+    }
+    async fn fetch_registrations_by_path(
+        &self,
+        path: &RegistrationPath,
+    ) -> Result<Vec<Registration>> {
+        Ok(self.registrations(|registration| below(registration, path)))
+    }
+    async fn fetch_registrations_by_path_and_metadata(
+        &self,
+        path: &RegistrationPath,
+        metadata: &HashMap<String, String>,
+    ) -> Result<Vec<Registration>> {
+        Ok(self.registrations(|registration| {
+            below(registration, path)
+                && metadata
+                    .iter()
+                    .all(|(key, value)| registration.metadata.get(key) == Some(value))
+        }))
+    }
+    async fn fetch_registration_by_iceberg_location(
+        &self,
+        iceberg_location: &IcebergLocation,
+    ) -> Result<Registration> {
+        self.registrations(|registration| {
+            &registration.iceberg_location.iceberg_location == iceberg_location
+        })
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            DiciError::NotFound(format!(
+                "Registration not found for icebergLocation {}",
+                iceberg_location
+            ))
+            .into()
+        })
+    }
+    async fn fetch_inventories_by_domain(&self, domain: &Domain) -> Result<Vec<Inventory>> {
+        Ok(self.inventories(|inventory| &inventory.id.domain.domain == domain))
+    }
+    async fn fetch_version(&self) -> Result<GitConfig> {
+        self.read()
+            .version
+            .clone()
+            .ok_or_else(|| DiciError::NotFound("Version information not found".to_string()).into())
+    }
+    async fn fetch_inventories_updated_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Inventory>> {
+        Ok(self.inventories(|inventory| inventory.updated_at >= since))
+    }
+    async fn fetch_inventories_by_iceberg_location_and_table(
+        &self,
+        iceberg_location: &IcebergLocation,
+        schema_table: &str,
+    ) -> Result<Vec<Inventory>> {
+        Ok(self.inventories(|inventory| in_table(inventory, iceberg_location, schema_table)))
+    }
+    async fn fetch_inventory_by_id(
+        &self,
+        domain: &Domain,
+        iceberg_location: &IcebergLocation,
+        schema_table: &str,
+    ) -> Result<Inventory> {
+        self.inventories(|inventory| {
+            &inventory.id.domain.domain == domain
+                && in_table(inventory, iceberg_location, schema_table)
+        })
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            DiciError::NotFound(format!(
+                "Inventory not found for domain={}, location={}, table={}",
+                domain, iceberg_location, schema_table
+            ))
+            .into()
+        })
+    }
+    async fn sync_table(
+        &self,
+        iceberg_location: &IcebergLocation,
+        schema_table: &str,
+    ) -> Result<Vec<Inventory>> {
+        Ok(self.touch(|inventory| in_table(inventory, iceberg_location, schema_table)))
+    }
+    async fn sync_table_domain(
+        &self,
+        domain: &Domain,
+        iceberg_location: &IcebergLocation,
+        schema_table: &str,
+    ) -> Result<Inventory> {
+        self.touch(|inventory| {
+            &inventory.id.domain.domain == domain
+                && in_table(inventory, iceberg_location, schema_table)
+        })
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            DiciError::NotFound(format!(
+                "Inventory not found for domain sync of {}/{}/{}",
+                domain, iceberg_location, schema_table
+            ))
+            .into()
+        })
+    }
+    async fn sync_iceberg_location(
+        &self,
+        iceberg_location: &IcebergLocation,
+    ) -> Result<IcebergLocationSync> {
+        let successes = self
+            .touch(|inventory| &inventory.id.iceberg_location.iceberg_location == iceberg_location);
+        if successes.is_empty() {
+            return Err(DiciError::NotFound(format!(
+                "Iceberg location {} not found",
+                iceberg_location
+            ))
+            .into());
+        }
+        Ok(IcebergLocationSync {
+            successes,
+            failures: vec![],
+        })
+    }
+}
@@ -0,0 +1,244 @@
+use crate::api::error::{ErrorKind, ErrorReport};
+use crate::api::http::management::api::ManagementApi;
+use crate::api::http::management::memory::InMemoryManagement;
+use crate::api::http::management::model::inventory::Inventory;
+use crate::api::http::management::model::registration::Registration;
+use crate::api::http::management::model::snapshot::ManagementSnapshot;
+use crate::api::http::management::model::sync::{
+    DomainIcebergAssetImpl, IcebergAssetImpl, IcebergLocationSync,
+};
+use crate::api::http::management::model::version::GitConfig;
+use crate::api::http::management::snapshot::SnapshotManagement;
+use crate::api::identifier::{Domain, FourByFour, IcebergLocation, RegistrationPath};
+use anyhow::{Context, Result, bail};
+use axum::extract::{Path as UrlPath, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+type Management = State<Arc<dyn ManagementApi>>;
+// Errors are answered the way dici management does, missing entries with 404 and bad identifiers with 400.
+struct MockError(anyhow::Error);
+impl<E: Into<anyhow::Error>> From<E> for MockError {
+    fn from(error: E) -> Self {
+        Self(error.into())
+    }
+}
+impl IntoResponse for MockError {
+    fn into_response(self) -> Response {
+        let report = ErrorReport::from(&self.0);
+        let status = match report.kind {
+            ErrorKind::NotFound => StatusCode::NOT_FOUND,
+            ErrorKind::BadIdentifier => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, Json(report)).into_response()
+    }
+}
+type Answer<T> = std::result::Result<Json<T>, MockError>;
+#[derive(Deserialize)]
+struct UpdatedSince {
+    since: DateTime<Utc>,
+}
+fn read_fixture<T: DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read fixture {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .map(Some)
+        .with_context(|| format!("Failed to parse fixture {}", path.display()))
+}
+// Either a file written by dici management export, or a directory with any of snapshot.json,
+// inventories.json, registrations.json and version.json. The separate files replace the matching
+// part of snapshot.json.
+pub fn read_fixtures(path: &Path) -> Result<ManagementSnapshot> {
+    if path.is_file() {
+        return SnapshotManagement::read(path);
+    }
+    if !path.is_dir() {
+        bail!("Fixtures {} not found", path.display());
+    }
+    let mut snapshot =
+        read_fixture::<ManagementSnapshot>(&path.join("snapshot.json"))?.unwrap_or_default();
+    if let Some(inventories) = read_fixture::<Vec<Inventory>>(&path.join("inventories.json"))? {
+        snapshot.inventories = inventories;
+    }
+    if let Some(registrations) =
+        read_fixture::<Vec<Registration>>(&path.join("registrations.json"))?
+    {
+        snapshot.registrations = registrations;
+    }
+    if let Some(version) = read_fixture::<GitConfig>(&path.join("version.json"))? {
+        snapshot.version = Some(version);
+    }
+    Ok(snapshot)
+}
+// Every endpoint ManagementClient calls, answered by the given implementation. Pointing a
+// ManagementClient at it round trips the json of every model.
+pub fn router(management: impl Into<Arc<dyn ManagementApi>>) -> Router {
+    Router::new()
+        .route("/inventory", get(inventories))
+        .route("/inventory/fxf/{fxf}", get(inventory_by_fxf))
+        .route(
+            "/inventory/iceberg/{iceberg_location}",
+            get(inventories_by_location),
+        )
+        .route(
+            "/inventory/iceberg/{iceberg_location}/{schema_table}",
+            get(inventories_by_table),
+        )
+        .route("/inventory/domain/{domain}", get(inventories_by_domain))
+        .route("/inventory/updated", get(inventories_updated_since))
+        .route(
+            "/inventory/id/{domain}/{iceberg_location}/{schema_table}",
+            get(inventory_by_id),
+        )
+        .route("/registration", get(registrations))
+        .route(
+            "/registration/iceberg/{iceberg_location}",
+            get(registration_by_location),
+        )
+        .route(
+            "/query/{*path}",
+            get(registrations_by_path).post(registrations_by_path_and_metadata),
+        )
+        .route("/version", get(version))
+        .route("/sync", post(sync_table))
+        .route("/sync/domain", post(sync_table_domain))
+        .route(
+            "/sync/iceberg/{iceberg_location}",
+            get(sync_iceberg_location),
+        )
+        .with_state(management.into())
+}
+// Serves the fixtures until the process is stopped, syncs change the state held in memory only.
+pub async fn serve(listener: TcpListener, management: InMemoryManagement) -> Result<()> {
+    axum::serve(listener, router(management))
+        .await
+        .context("Mock dici management stopped")
+}
+async fn inventories(State(management): Management) -> Answer<Vec<Inventory>> {
+    Ok(Json(management.fetch_inventories().await?))
+}
+async fn inventory_by_fxf(
+    State(management): Management,
+    UrlPath(fxf): UrlPath<FourByFour>,
+) -> Answer<Inventory> {
+    Ok(Json(management.fetch_inventory_by_fxf(&fxf).await?))
+}
+async fn inventories_by_location(
+    State(management): Management,
+    UrlPath(iceberg_location): UrlPath<IcebergLocation>,
+) -> Answer<Vec<Inventory>> {
+    Ok(Json(
+        management
+            .fetch_inventories_by_iceberg_location(&iceberg_location)
+            .await?,
+    ))
+}
+async fn inventories_by_table(
+    State(management): Management,
+    UrlPath((iceberg_location, schema_table)): UrlPath<(IcebergLocation, String)>,
+) -> Answer<Vec<Inventory>> {
+    Ok(Json(
+        management
+            .fetch_inventories_by_iceberg_location_and_table(&iceberg_location, &schema_table)
+            .await?,
+    ))
+}
+async fn inventories_by_domain(
+    State(management): Management,
+    UrlPath(domain): UrlPath<Domain>,
+) -> Answer<Vec<Inventory>> {
+    Ok(Json(management.fetch_inventories_by_domain(&domain).await?))
+}
+async fn inventories_updated_since(
+    State(management): Management,
+    Query(UpdatedSince { since }): Query<UpdatedSince>,
+) -> Answer<Vec<Inventory>> {
+    Ok(Json(
+        management.fetch_inventories_updated_since(since).await?,
+    ))
+}
+async fn inventory_by_id(
+    State(management): Management,
+    UrlPath((domain, iceberg_location, schema_table)): UrlPath<(Domain, IcebergLocation, String)>,
+) -> Answer<Inventory> {
+    Ok(Json(
+        management
+            .fetch_inventory_by_id(&domain, &iceberg_location, &schema_table)
+            .await?,
+    ))
+}
+async fn registrations(State(management): Management) -> Answer<Vec<Registration>> {
+    Ok(Json(management.fetch_registrations().await?))
+}
+async fn registration_by_location(
+    State(management): Management,
+    UrlPath(iceberg_location): UrlPath<IcebergLocation>,
+) -> Answer<Registration> {
+    Ok(Json(
+        management
+            .fetch_registration_by_iceberg_location(&iceberg_location)
+            .await?,
+    ))
+}
+async fn registrations_by_path(
+    State(management): Management,
+    UrlPath(path): UrlPath<String>,
+) -> Answer<Vec<Registration>> {
+    let path: RegistrationPath = path.parse()?;
+    Ok(Json(management.fetch_registrations_by_path(&path).await?))
+}
+async fn registrations_by_path_and_metadata(
+    State(management): Management,
+    UrlPath(path): UrlPath<String>,
+    Json(metadata): Json<HashMap<String, String>>,
+) -> Answer<Vec<Registration>> {
+    let path: RegistrationPath = path.parse()?;
+    Ok(Json(
+        management
+            .fetch_registrations_by_path_and_metadata(&path, &metadata)
+            .await?,
+    ))
+}
+async fn version(State(management): Management) -> Answer<GitConfig> {
+    Ok(Json(management.fetch_version().await?))
+}
+async fn sync_table(
+    State(management): Management,
+    Json(asset): Json<IcebergAssetImpl>,
+) -> Answer<Vec<Inventory>> {
+    Ok(Json(
+        management
+            .sync_table(&asset.iceberg_location, &asset.schema_table)
+            .await?,
+    ))
+}
+async fn sync_table_domain(
+    State(management): Management,
+    Json(asset): Json<DomainIcebergAssetImpl>,
+) -> Answer<Inventory> {
+    Ok(Json(
+        management
+            .sync_table_domain(&asset.domain, &asset.iceberg_location, &asset.schema_table)
+            .await?,
+    ))
+}
+async fn sync_iceberg_location(
+    State(management): Management,
+    UrlPath(iceberg_location): UrlPath<IcebergLocation>,
+) -> Answer<IcebergLocationSync> {
+    Ok(Json(
+        management.sync_iceberg_location(&iceberg_location).await?,
+    ))
+}
@@ -1,3 +1,31 @@
+pub mod api;
+pub mod cache;
+pub mod cassette;
 pub mod client;
 pub mod config;
+pub mod memory;
+#[cfg(feature = "dev")]
+pub mod mock;
 pub mod model;
+pub mod snapshot;
+use anyhow::{Context, Result};
+use reqwest::header::HeaderMap;
+use reqwest::{Response, StatusCode};
+// Reads the response to keep what `keep` takes from it, and hands back an identical one.
+pub async fn buffer_response<T>(
+    response: Response,
+    keep: impl FnOnce(StatusCode, &HeaderMap, &[u8]) -> T,
+) -> Result<(T, Response)> {
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = response
+        .bytes()
+        .await
+        .context("Failed to read dici management response")?;
+    let kept = keep(status, &headers, &bytes);
+    let mut replayed = http::Response::builder().status(status);
+    if let Some(replayed_headers) = replayed.headers_mut() {
+        *replayed_headers = headers;
+    }
+    Ok((kept, Response::from(replayed.body(bytes)?)))
+}
@@ -1,6 +1,7 @@
+use crate::api::identifier;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Inventory {
     pub id: Id,
@@ -8,30 +9,33 @@ pub struct Inventory {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Id {
     pub domain: Domain,
     pub iceberg_location: IcebergLocation,
     pub schema_table: SchemaTable,
 }
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Domain {
-    pub domain: String,
+    #[serde(deserialize_with = "identifier::lenient")]
+    pub domain: identifier::Domain,
 }
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct IcebergLocation {
-    pub iceberg_location: String,
+    #[serde(deserialize_with = "identifier::lenient")]
+    pub iceberg_location: identifier::IcebergLocation,
 }
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SchemaTable {
     pub schema_table: String,
 }
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct FourByFour {
-    pub four_by_four: String,
+    #[serde(deserialize_with = "identifier::lenient")]
+    pub four_by_four: identifier::FourByFour,
 }
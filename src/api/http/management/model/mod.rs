@@ -1,4 +1,5 @@
 pub mod inventory;
 pub mod registration;
+pub mod snapshot;
 pub mod sync;
 pub mod version;
@@ -1,7 +1,8 @@
+use crate::api::identifier;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Registration {
     pub id: Id,
@@ -10,12 +11,20 @@ pub struct Registration {
     pub updated_at: DateTime<Utc>,
     pub metadata: HashMap<String, String>,
 }
-#[derive(Serialize, Deserialize, Debug)]
+impl Registration {
+    // A path is registered for one icebergLocation only, so registrations are told apart by path.
+    pub fn key(&self) -> &identifier::RegistrationPath {
+        &self.id.path
+    }
+}
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Id {
-    pub path: String,
+    #[serde(deserialize_with = "identifier::lenient")]
+    pub path: identifier::RegistrationPath,
 }
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct IcebergLocation {
-    pub iceberg_location: String,
+    #[serde(deserialize_with = "identifier::lenient")]
+    pub iceberg_location: identifier::IcebergLocation,
 }
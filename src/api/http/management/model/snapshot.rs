@@ -0,0 +1,16 @@
+use crate::api::http::management::model::inventory::Inventory;
+use crate::api::http::management::model::registration::Registration;
+use crate::api::http::management::model::version::GitConfig;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+// Everything dici management knows at one point in time, written by dici management export.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagementSnapshot {
+    pub exported_at: Option<DateTime<Utc>>,
+    pub version: Option<GitConfig>,
+    #[serde(default)]
+    pub inventories: Vec<Inventory>,
+    #[serde(default)]
+    pub registrations: Vec<Registration>,
+}
@@ -1,25 +1,29 @@
 use crate::api::http::management::model::inventory::Inventory;
+use crate::api::identifier::{Domain, IcebergLocation, lenient};
 use serde::{Deserialize, Serialize};
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct IcebergAssetImpl {
-    pub iceberg_location: String,
+    #[serde(deserialize_with = "lenient")]
+    pub iceberg_location: IcebergLocation,
     pub schema_table: String,
 }
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct DomainIcebergAssetImpl {
-    pub domain: String,
-    pub iceberg_location: String,
+    #[serde(deserialize_with = "lenient")]
+    pub domain: Domain,
+    #[serde(deserialize_with = "lenient")]
+    pub iceberg_location: IcebergLocation,
     pub schema_table: String,
 }
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct IcebergLocationSync {
     pub successes: Vec<Inventory>,
     pub failures: Vec<Failed>,
 }
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Failed {
     pub inventory: Inventory,
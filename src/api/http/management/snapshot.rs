@@ -0,0 +1,365 @@
+use crate::api::http::management::api::ManagementApi;
+use crate::api::http::management::memory::InMemoryManagement;
+use crate::api::http::management::model::inventory::Inventory;
+use crate::api::http::management::model::registration::Registration;
+use crate::api::http::management::model::snapshot::ManagementSnapshot;
+use crate::api::http::management::model::sync::IcebergLocationSync;
+use crate::api::http::management::model::version::GitConfig;
+use crate::api::identifier::{Domain, FourByFour, IcebergLocation, RegistrationPath};
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::join;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+// The inventory id, <domain>/<icebergLocation>/<schemaTable>.
+fn inventory_key(inventory: &Inventory) -> String {
+    format!(
+        "{}/{}/{}",
+        inventory.id.domain.domain,
+        inventory.id.iceberg_location.iceberg_location,
+        inventory.id.schema_table.schema_table
+    )
+}
+fn registration_key(registration: &Registration) -> RegistrationPath {
+    registration.key().clone()
+}
+// Entries by key, two entries with the same key would hide one of them from the diff.
+fn keyed<'a, T, K: Ord + Display>(
+    entries: &'a [T],
+    key: &impl Fn(&T) -> K,
+    kind: &str,
+) -> Result<BTreeMap<K, &'a T>> {
+    let mut keyed = BTreeMap::new();
+    for entry in entries {
+        let key = key(entry);
+        if keyed.contains_key(&key) {
+            bail!("The snapshot has more than one {} {}", kind, key);
+        }
+        keyed.insert(key, entry);
+    }
+    Ok(keyed)
+}
+#[derive(Serialize, Debug, Clone)]
+pub struct Changed<T> {
+    pub before: T,
+    pub after: T,
+}
+#[derive(Serialize, Debug, Clone)]
+pub struct Changes<T> {
+    pub added: Vec<T>,
+    pub removed: Vec<T>,
+    pub changed: Vec<Changed<T>>,
+}
+impl<T: Serialize + Clone> Changes<T> {
+    // Entries are matched by key and compared by their json, the models don't implement PartialEq.
+    fn between<K: Ord + Display>(
+        before: &[T],
+        after: &[T],
+        key: impl Fn(&T) -> K,
+        kind: &str,
+    ) -> Result<Self> {
+        let before = keyed(before, &key, kind)?;
+        let after = keyed(after, &key, kind)?;
+        let json = |entry: &T| serde_json::to_value(entry).unwrap_or(Value::Null);
+        Ok(Self {
+            added: after
+                .iter()
+                .filter(|(key, _)| !before.contains_key(key))
+                .map(|(_, entry)| (*entry).clone())
+                .collect(),
+            removed: before
+                .iter()
+                .filter(|(key, _)| !after.contains_key(key))
+                .map(|(_, entry)| (*entry).clone())
+                .collect(),
+            changed: before
+                .iter()
+                .filter_map(|(key, old)| Some((*old, *after.get(key)?)))
+                .filter(|(old, new)| json(old) != json(new))
+                .map(|(old, new)| Changed {
+                    before: old.clone(),
+                    after: new.clone(),
+                })
+                .collect(),
+        })
+    }
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+// What changed between two exports, inventories are matched by id and registrations by path. Fails
+// when a snapshot holds the same inventory or registration twice.
+#[derive(Serialize, Debug, Clone)]
+pub struct SnapshotDiff {
+    pub inventories: Changes<Inventory>,
+    pub registrations: Changes<Registration>,
+}
+impl SnapshotDiff {
+    pub fn between(before: &ManagementSnapshot, after: &ManagementSnapshot) -> Result<Self> {
+        Ok(Self {
+            inventories: Changes::between(
+                &before.inventories,
+                &after.inventories,
+                inventory_key,
+                "inventory",
+            )?,
+            registrations: Changes::between(
+                &before.registrations,
+                &after.registrations,
+                registration_key,
+                "registration",
+            )?,
+        })
+    }
+    pub fn is_empty(&self) -> bool {
+        self.inventories.is_empty() && self.registrations.is_empty()
+    }
+}
+// Answers from a file written by dici management export, read on first use. Read only, syncs fail.
+#[derive(Clone)]
+pub struct SnapshotManagement {
+    path: PathBuf,
+    loaded: Arc<OnceLock<InMemoryManagement>>,
+}
+impl SnapshotManagement {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            loaded: Arc::default(),
+        }
+    }
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    pub fn read(path: &Path) -> Result<ManagementSnapshot> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read management snapshot {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse management snapshot {}", path.display()))
+    }
+    // Everything dici management knows right now, sorted so exports of the same state are identical.
+    pub async fn export(management: &dyn ManagementApi) -> Result<ManagementSnapshot> {
+        let (inventories, registrations, version) = join!(
+            management.fetch_inventories(),
+            management.fetch_registrations(),
+            management.fetch_version()
+        );
+        let (mut inventories, mut registrations) = (inventories?, registrations?);
+        inventories.sort_by_key(inventory_key);
+        registrations.sort_by_key(registration_key);
+        Ok(ManagementSnapshot {
+            exported_at: Some(Utc::now()),
+            version: version.ok(),
+            inventories,
+            registrations,
+        })
+    }
+    fn loaded(&self) -> Result<&InMemoryManagement> {
+        if let Some(loaded) = self.loaded.get() {
+            return Ok(loaded);
+        }
+        let loaded = InMemoryManagement::from(Self::read(&self.path)?);
+        Ok(self.loaded.get_or_init(|| loaded))
+    }
+    fn read_only<T>(&self) -> Result<T> {
+        bail!(
+            "Management snapshot {} is read only, syncs need dici management",
+            self.path.display()
+        )
+    }
+}
+#[async_trait]
+impl ManagementApi for SnapshotManagement {
+    async fn fetch_inventories(&self) -> Result<Vec<Inventory>> {
+        self.loaded()?.fetch_inventories().await
+    }
+    async fn fetch_inventory_by_fxf(&self, fxf: &FourByFour) -> Result<Inventory> {
+        self.loaded()?.fetch_inventory_by_fxf(fxf).await
+    }
+    async fn fetch_inventories_by_iceberg_location(
+        &self,
+        iceberg_location: &IcebergLocation,
+    ) -> Result<Vec<Inventory>> {
+        self.loaded()?
+            .fetch_inventories_by_iceberg_location(iceberg_location)
+            .await
+    }
+    async fn fetch_registrations(&self) -> Result<Vec<Registration>> {
+        self.loaded()?.fetch_registrations().await
+    }
+    async fn fetch_registrations_by_path(
+        &self,
+        path: &RegistrationPath,
+    ) -> Result<Vec<Registration>> {
+        self.loaded()?.fetch_registrations_by_path(path).await
+    }
+    async fn fetch_registrations_by_path_and_metadata(
+        &self,
+        path: &RegistrationPath,
+        metadata: &HashMap<String, String>,
+    ) -> Result<Vec<Registration>> {
+        self.loaded()?
+            .fetch_registrations_by_path_and_metadata(path, metadata)
+            .await
+    }
+    async fn fetch_registration_by_iceberg_location(
+        &self,
+        iceberg_location: &IcebergLocation,
+    ) -> Result<Registration> {
+        self.loaded()?
+            .fetch_registration_by_iceberg_location(iceberg_location)
+            .await
+    }
+    async fn fetch_inventories_by_domain(&self, domain: &Domain) -> Result<Vec<Inventory>> {
+        self.loaded()?.fetch_inventories_by_domain(domain).await
+    }
+    async fn fetch_version(&self) -> Result<GitConfig> {
+        self.loaded()?.fetch_version().await
+    }
+    async fn fetch_inventories_updated_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Inventory>> {
+        self.loaded()?.fetch_inventories_updated_since(since).await
+    }
+    async fn fetch_inventories_by_iceberg_location_and_table(
+        &self,
+        iceberg_location: &IcebergLocation,
+        schema_table: &str,
+    ) -> Result<Vec<Inventory>> {
+        self.loaded()?
+            .fetch_inventories_by_iceberg_location_and_table(iceberg_location, schema_table)
+            .await
+    }
+    async fn fetch_inventory_by_id(
+        &self,
+        domain: &Domain,
+        iceberg_location: &IcebergLocation,
+        schema_table: &str,
+    ) -> Result<Inventory> {
+        self.loaded()?
+            .fetch_inventory_by_id(domain, iceberg_location, schema_table)
+            .await
+    }
+    async fn sync_table(
+        &self,
+        _iceberg_location: &IcebergLocation,
+        _schema_table: &str,
+    ) -> Result<Vec<Inventory>> {
+        self.read_only()
+    }
+    async fn sync_table_domain(
+        &self,
+        _domain: &Domain,
+        _iceberg_location: &IcebergLocation,
+        _schema_table: &str,
+    ) -> Result<Inventory> {
+        self.read_only()
+    }
+    async fn sync_iceberg_location(
+        &self,
+        _iceberg_location: &IcebergLocation,
+    ) -> Result<IcebergLocationSync> {
+        self.read_only()
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    fn inventory(fxf: &str, schema_table: &str) -> Inventory {
+        changed_at(fxf, schema_table, "2024-01-01T00:00:00Z")
+    }
+    fn changed_at(fxf: &str, schema_table: &str, updated_at: &str) -> Inventory {
+        serde_json::from_value(json!({
+            "id": {
+                "domain": {"domain": "data.example.gov"},
+                "icebergLocation": {"icebergLocation": "_0123456789abcdef0123456789abcdef"},
+                "schemaTable": {"schemaTable": schema_table}
+            },
+            "fourByFour": {"fourByFour": fxf},
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": updated_at
+        }))
+        .unwrap()
+    }
+    fn registration(path: &str) -> Registration {
+        serde_json::from_value(json!({
+            "id": {"path": path},
+            "icebergLocation": {"icebergLocation": "_0123456789abcdef0123456789abcdef"},
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z",
+            "metadata": {}
+        }))
+        .unwrap()
+    }
+    fn snapshot(
+        inventories: Vec<Inventory>,
+        registrations: Vec<Registration>,
+    ) -> ManagementSnapshot {
+        ManagementSnapshot {
+            inventories,
+            registrations,
+            ..ManagementSnapshot::default()
+        }
+    }
+    #[test]
+    fn reports_added_removed_and_changed_entries() {
+        let before = snapshot(
+            vec![
+                inventory("abcd-1234", "a.kept"),
+                inventory("abcd-5678", "a.removed"),
+                inventory("abcd-9012", "a.changed"),
+            ],
+            vec![registration("erp/kept"), registration("erp/removed")],
+        );
+        let changed = changed_at("abcd-9012", "a.changed", "2024-02-01T00:00:00Z");
+        let after = snapshot(
+            vec![
+                inventory("abcd-1234", "a.kept"),
+                changed.clone(),
+                inventory("abcd-3456", "a.added"),
+            ],
+            vec![registration("erp/kept"), registration("erp/added")],
+        );
+        let diff = SnapshotDiff::between(&before, &after).unwrap();
+        let tables = |inventories: &[Inventory]| {
+            inventories
+                .iter()
+                .map(|inventory| inventory.id.schema_table.schema_table.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(tables(&diff.inventories.added), ["a.added"]);
+        assert_eq!(tables(&diff.inventories.removed), ["a.removed"]);
+        assert_eq!(diff.inventories.changed.len(), 1);
+        assert_eq!(
+            diff.inventories.changed[0].after.updated_at,
+            changed.updated_at
+        );
+        assert_eq!(diff.registrations.added[0].key().as_str(), "erp/added");
+        assert_eq!(diff.registrations.removed[0].key().as_str(), "erp/removed");
+        assert!(diff.registrations.changed.is_empty());
+        assert!(SnapshotDiff::between(&after, &after).unwrap().is_empty());
+    }
+    #[test]
+    fn refuses_duplicate_keys() {
+        let empty = snapshot(vec![], vec![]);
+        let duplicated = snapshot(vec![], vec![registration("erp/a"), registration("/erp/a")]);
+        let error = SnapshotDiff::between(&duplicated, &empty).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("more than one registration erp/a")
+        );
+        let duplicated = snapshot(
+            vec![inventory("abcd-1234", "a.b"), inventory("abcd-5678", "a.b")],
+            vec![],
+        );
+        assert!(SnapshotDiff::between(&empty, &duplicated).is_err());
+    }
+}
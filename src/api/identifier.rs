@@ -0,0 +1,189 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum IdentifierError {
+    #[error(
+        "Invalid fourByFour '{0}', expected xxxx-xxxx with lowercase letters and digits, e.g. abcd-1234"
+    )]
+    FourByFour(String),
+    #[error(
+        "Invalid icebergLocation '{0}', expected _ followed by 32 lowercase hex characters, e.g. _0123456789abcdef0123456789abcdef"
+    )]
+    IcebergLocation(String),
+    #[error(
+        "Invalid domain '{0}', expected a dotted host name without slashes, e.g. data.example.gov"
+    )]
+    Domain(String),
+    #[error(
+        "Invalid registration path '{0}', expected folder names separated by single slashes, e.g. erp_pro_10/incode"
+    )]
+    RegistrationPath(String),
+    #[error(
+        "Unrecognized identifier '{0}', expected a fourByFour, icebergLocation, <icebergLocation>/<schemaTable>, domain, inventory id, registration path or asset"
+    )]
+    Unrecognized(String),
+    #[error("Alias '{0}' is given to more than one asset, each asset needs its own alias")]
+    DuplicateAlias(String),
+}
+// Builds an identifier without validating it, for values dici management hands back rather than the user.
+pub trait Unchecked {
+    fn unchecked(value: String) -> Self;
+}
+// Deserializes an identifier as is, so one odd record in a dici management response doesn't fail a whole
+// list. Use it on response models only, user input goes through FromStr.
+pub fn lenient<'de, D: Deserializer<'de>, T: Unchecked>(deserializer: D) -> Result<T, D::Error> {
+    String::deserialize(deserializer).map(T::unchecked)
+}
+// A validated string, built through FromStr or TryFrom<String> so every value has the documented format.
+// The optional normalization runs first, on validated and unchecked values alike.
+macro_rules! identifier {
+    ($name:ident, $valid:expr) => {
+        identifier!($name, std::convert::identity, $valid);
+    };
+    ($name:ident, $normalize:expr, $valid:expr) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(try_from = "String", into = "String")]
+        pub struct $name(String);
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+        impl TryFrom<String> for $name {
+            type Error = IdentifierError;
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                let value = $normalize(value);
+                if $valid(value.as_str()) {
+                    Ok(Self(value))
+                } else {
+                    Err(IdentifierError::$name(value))
+                }
+            }
+        }
+        impl Unchecked for $name {
+            fn unchecked(value: String) -> Self {
+                Self($normalize(value))
+            }
+        }
+        impl FromStr for $name {
+            type Err = IdentifierError;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.to_string().try_into()
+            }
+        }
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+    };
+}
+// xxxx-xxxx, e.g. abcd-1234.
+identifier!(FourByFour, |s: &str| {
+    s.len() == 9
+        && s.char_indices().all(|(i, c)| match i {
+            4 => c == '-',
+            _ => c.is_ascii_lowercase() || c.is_ascii_digit(),
+        })
+});
+// "_" followed by the 32 lowercase hex chars of the md5 of a registration path.
+identifier!(IcebergLocation, |s: &str| {
+    s.len() == 33
+        && s.strip_prefix('_').is_some_and(|hex| {
+            hex.chars()
+                .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+        })
+});
+// A host name such as data.example.gov.
+identifier!(Domain, |s: &str| {
+    s.contains('.')
+        && s.split('.').all(|label| {
+            !label.is_empty()
+                && label
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+});
+// Folder names separated by slashes such as erp_pro_10/incode, a prefix of a path matches the registrations below it.
+// One leading slash is dropped, so /erp_pro_10/incode is the same path.
+identifier!(
+    RegistrationPath,
+    |value: String| match value.strip_prefix('/') {
+        Some(path) => path.to_string(),
+        None => value,
+    },
+    |s: &str| {
+        s.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && !segment.chars().any(|c| c.is_whitespace() || c.is_control())
+        })
+    }
+);
+impl RegistrationPath {
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.0.split('/')
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn validates_formats() {
+        assert!("abcd-1234".parse::<FourByFour>().is_ok());
+        assert!("ABCD-1234".parse::<FourByFour>().is_err());
+        assert!(
+            "_0123456789abcdef0123456789abcdef"
+                .parse::<IcebergLocation>()
+                .is_ok()
+        );
+        assert!(
+            "_0123456789ABCDEF0123456789abcdef"
+                .parse::<IcebergLocation>()
+                .is_err()
+        );
+        assert!("data.example.gov".parse::<Domain>().is_ok());
+        assert!("localhost".parse::<Domain>().is_err());
+        for path in ["erp", "erp/../etc", "erp//incode", "erp/in code"] {
+            assert_eq!(path.parse::<RegistrationPath>().is_ok(), path == "erp");
+        }
+    }
+    #[test]
+    fn drops_one_leading_slash_from_registration_paths() {
+        let path: RegistrationPath = "/erp_pro_10/incode".parse().unwrap();
+        assert_eq!(path.as_str(), "erp_pro_10/incode");
+        assert_eq!(
+            path.segments().collect::<Vec<_>>(),
+            ["erp_pro_10", "incode"]
+        );
+        assert!("//erp".parse::<RegistrationPath>().is_err());
+        assert_eq!(
+            RegistrationPath::unchecked("/a b".to_string()).as_str(),
+            "a b"
+        );
+    }
+    #[test]
+    fn lenient_accepts_what_validation_refuses() {
+        #[derive(Deserialize)]
+        struct Response {
+            #[serde(deserialize_with = "lenient")]
+            fxf: FourByFour,
+        }
+        let response: Response = serde_json::from_str(r#"{"fxf": "NOT-VALID"}"#).unwrap();
+        assert_eq!(response.fxf.as_str(), "NOT-VALID");
+        assert!(serde_json::from_str::<FourByFour>(r#""NOT-VALID""#).is_err());
+    }
+}
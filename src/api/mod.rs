@@ -1,2 +1,14 @@
+pub mod audit;
+pub mod bulk_sync;
+pub mod config;
+pub mod context;
+pub mod error;
+pub mod fs;
 pub mod http;
+pub mod identifier;
+pub mod lock;
+pub mod resolve;
+pub mod retry;
+pub mod since;
 pub mod store;
+pub mod watch;
@@ -0,0 +1,564 @@
+use crate::api::error::{DiciError, ErrorKind};
+use crate::api::http::management::api::ManagementApi;
+use crate::api::http::management::model::inventory::Inventory;
+use crate::api::http::management::model::registration::Registration;
+use crate::api::identifier::{
+    Domain, FourByFour, IcebergLocation, IdentifierError, RegistrationPath,
+};
+use crate::api::store::asset::dici::DiciAsset;
+use crate::api::store::catalog::catalog_source::CatalogSource;
+use crate::api::store::catalog::dici::DiciCatalog;
+use anyhow::{Context, Result};
+use futures::future::join_all;
+use iceberg::TableIdent;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+// Any identifier dici knows about, detected from its format.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum Identifier {
+    FourByFour {
+        four_by_four: FourByFour,
+    },
+    IcebergLocation {
+        iceberg_location: IcebergLocation,
+    },
+    IcebergAsset {
+        iceberg_location: IcebergLocation,
+        schema_table: String,
+    },
+    Inventory {
+        domain: Domain,
+        iceberg_location: IcebergLocation,
+        schema_table: String,
+    },
+    Domain {
+        domain: Domain,
+    },
+    RegistrationPath {
+        path: RegistrationPath,
+    },
+}
+impl Identifier {
+    // The icebergLocation named by the identifier itself, if any.
+    fn iceberg_location(&self) -> Option<&IcebergLocation> {
+        match self {
+            Identifier::IcebergLocation { iceberg_location }
+            | Identifier::IcebergAsset {
+                iceberg_location, ..
+            }
+            | Identifier::Inventory {
+                iceberg_location, ..
+            } => Some(iceberg_location),
+            _ => None,
+        }
+    }
+}
+// Assets in their string form are taken as is, otherwise the first matching format wins in the order
+// fourByFour, icebergLocation, <icebergLocation>/<schemaTable> (or with a dot), domain,
+// <domain>/<icebergLocation>/<schemaTable> and registration path. reg:<path> forces a registration path.
+impl FromStr for Identifier {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Some(path) = s.strip_prefix("reg:").filter(|rest| !rest.contains('#')) {
+            return Ok(Identifier::RegistrationPath {
+                path: path.parse()?,
+            });
+        }
+        if let Some(("core" | "iceberg" | "inv" | "reg", _)) = s.split_once(':') {
+            return Ok(match s.parse::<DiciAsset>()? {
+                DiciAsset::Core(args) => Identifier::FourByFour {
+                    four_by_four: args.asset().fxf.clone(),
+                },
+                DiciAsset::Iceberg(args) => Identifier::IcebergAsset {
+                    iceberg_location: args.asset().location.clone(),
+                    schema_table: args.asset().schema_table.clone(),
+                },
+                DiciAsset::Inventory(args) => Identifier::Inventory {
+                    domain: args.asset().domain.clone(),
+                    iceberg_location: args.asset().location.clone(),
+                    schema_table: args.asset().schema_table.clone(),
+                },
+                DiciAsset::Registration(args) => Identifier::RegistrationPath {
+                    path: args.asset().path.clone(),
+                },
+            });
+        }
+        if let Ok(four_by_four) = s.parse() {
+            return Ok(Identifier::FourByFour { four_by_four });
+        }
+        if let Ok(iceberg_location) = s.parse() {
+            return Ok(Identifier::IcebergLocation { iceberg_location });
+        }
+        if let Some((iceberg_location, schema_table)) = s
+            .split_once('/')
+            .or_else(|| s.split_once('.'))
+            .and_then(|(location, table)| Some((location.parse().ok()?, table)))
+            .filter(|(_, table)| !table.is_empty() && !table.contains('/'))
+        {
+            return Ok(Identifier::IcebergAsset {
+                iceberg_location,
+                schema_table: schema_table.to_string(),
+            });
+        }
+        if let Ok(domain) = s.parse() {
+            return Ok(Identifier::Domain { domain });
+        }
+        if let [domain, location, schema_table] = s.splitn(3, '/').collect::<Vec<_>>().as_slice()
+            && let (Ok(domain), Ok(iceberg_location)) = (domain.parse(), location.parse())
+            && !schema_table.is_empty()
+        {
+            return Ok(Identifier::Inventory {
+                domain,
+                iceberg_location,
+                schema_table: schema_table.to_string(),
+            });
+        }
+        s.parse()
+            .map(|path| Identifier::RegistrationPath { path })
+            .map_err(|_| IdentifierError::Unrecognized(s.to_string()).into())
+    }
+}
+impl Display for Identifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Identifier::FourByFour { four_by_four } => write!(f, "{}", four_by_four),
+            Identifier::IcebergLocation { iceberg_location } => write!(f, "{}", iceberg_location),
+            Identifier::IcebergAsset {
+                iceberg_location,
+                schema_table,
+            } => write!(f, "{}/{}", iceberg_location, schema_table),
+            Identifier::Inventory {
+                domain,
+                iceberg_location,
+                schema_table,
+            } => write!(f, "{}/{}/{}", domain, iceberg_location, schema_table),
+            Identifier::Domain { domain } => write!(f, "{}", domain),
+            Identifier::RegistrationPath { path } => write!(f, "reg:{}", path),
+        }
+    }
+}
+// A table reached from the identifier and its state in the warehouse catalog.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedTable {
+    pub iceberg_location: IcebergLocation,
+    pub schema_table: String,
+    pub exists: bool,
+    pub current_snapshot_id: Option<i64>,
+    // When the current snapshot was committed.
+    pub snapshot_timestamp_ms: Option<i64>,
+    pub last_updated_ms: Option<i64>,
+    // Why the table couldn't be checked, exists is false then but the table may well be there.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+impl ResolvedTable {
+    fn failed(
+        iceberg_location: IcebergLocation,
+        schema_table: String,
+        error: &anyhow::Error,
+    ) -> Self {
+        Self {
+            iceberg_location,
+            schema_table,
+            exists: false,
+            current_snapshot_id: None,
+            snapshot_timestamp_ms: None,
+            last_updated_ms: None,
+            error: Some(format!("{:#}", error)),
+        }
+    }
+    pub async fn resolve(
+        dici_catalog: &DiciCatalog,
+        iceberg_location: IcebergLocation,
+        schema_table: String,
+    ) -> Result<Self> {
+        let ident = TableIdent::from_strs([iceberg_location.as_str(), schema_table.as_str()])
+            .context("Failed to construct table ident")?;
+        let exists = dici_catalog
+            .catalog()
+            .await
+            .context("Failed to construct catalog")?
+            .table_exists(&ident)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to check whether table {}/{} exists",
+                    iceberg_location, schema_table
+                )
+            })?;
+        let (current_snapshot_id, snapshot_timestamp_ms, last_updated_ms) = match exists {
+            true => {
+                let table = dici_catalog.load_table(&ident).await?;
+                let metadata = table.metadata();
+                (
+                    metadata.current_snapshot_id(),
+                    metadata
+                        .current_snapshot()
+                        .map(|snapshot| snapshot.timestamp_ms()),
+                    Some(metadata.last_updated_ms()),
+                )
+            }
+            false => (None, None, None),
+        };
+        Ok(Self {
+            iceberg_location,
+            schema_table,
+            exists,
+            current_snapshot_id,
+            snapshot_timestamp_ms,
+            last_updated_ms,
+            error: None,
+        })
+    }
+}
+// Everything connected to an identifier: the inventories it names, the registrations of their locations,
+// the other inventories sharing those locations, e.g. in other domains, and the tables behind them.
+// Only the lookups of the identifier itself fail the resolve, the others that fail are listed in errors
+// and leave the graph partial.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityGraph {
+    pub identifier: Identifier,
+    pub inventories: Vec<Inventory>,
+    pub registrations: Vec<Registration>,
+    pub siblings: Vec<Inventory>,
+    pub tables: Vec<ResolvedTable>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+// Keeps what succeeded and records why the others failed.
+fn partition<T>(results: Vec<Result<T>>, errors: &mut Vec<String>) -> Vec<T> {
+    results
+        .into_iter()
+        .filter_map(|result| result.map_err(|e| errors.push(format!("{:#}", e))).ok())
+        .collect()
+}
+impl IdentityGraph {
+    pub async fn resolve(
+        identifier: Identifier,
+        management_client: &dyn ManagementApi,
+        dici_catalog: &DiciCatalog,
+    ) -> Result<Self> {
+        let mut errors = vec![];
+        let (inventories, mut registrations) = match &identifier {
+            Identifier::FourByFour { four_by_four } => (
+                vec![
+                    management_client
+                        .fetch_inventory_by_fxf(four_by_four)
+                        .await?,
+                ],
+                vec![],
+            ),
+            Identifier::IcebergLocation { iceberg_location } => (
+                management_client
+                    .fetch_inventories_by_iceberg_location(iceberg_location)
+                    .await?,
+                vec![],
+            ),
+            Identifier::IcebergAsset {
+                iceberg_location,
+                schema_table,
+            } => (
+                management_client
+                    .fetch_inventories_by_iceberg_location_and_table(iceberg_location, schema_table)
+                    .await?,
+                vec![],
+            ),
+            Identifier::Inventory {
+                domain,
+                iceberg_location,
+                schema_table,
+            } => (
+                vec![
+                    management_client
+                        .fetch_inventory_by_id(domain, iceberg_location, schema_table)
+                        .await?,
+                ],
+                vec![],
+            ),
+            // Registrations carry their domain in metadata["domain"].
+            Identifier::Domain { domain } => (
+                management_client
+                    .fetch_inventories_by_domain(domain)
+                    .await?,
+                management_client
+                    .fetch_registrations()
+                    .await?
+                    .into_iter()
+                    .filter(|registration| {
+                        registration.metadata.get("domain").map(String::as_str)
+                            == Some(domain.as_str())
+                    })
+                    .collect(),
+            ),
+            Identifier::RegistrationPath { path } => {
+                let registrations = management_client.fetch_registrations_by_path(path).await?;
+                if registrations.is_empty() {
+                    return Err(DiciError::NotFound(format!(
+                        "Registration not found for path {}",
+                        path
+                    ))
+                    .into());
+                }
+                let inventories = join_all(registrations.iter().map(|registration| {
+                    management_client.fetch_inventories_by_iceberg_location(
+                        &registration.iceberg_location.iceberg_location,
+                    )
+                }))
+                .await;
+                let inventories = partition(inventories, &mut errors)
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                (inventories, registrations)
+            }
+        };
+        let locations: BTreeSet<IcebergLocation> = inventories
+            .iter()
+            .map(|inventory| inventory.id.iceberg_location.iceberg_location.clone())
+            .chain(
+                registrations
+                    .iter()
+                    .map(|registration| registration.iceberg_location.iceberg_location.clone()),
+            )
+            .chain(identifier.iceberg_location().cloned())
+            .collect();
+        let registered: BTreeSet<IcebergLocation> = registrations
+            .iter()
+            .map(|registration| registration.iceberg_location.iceberg_location.clone())
+            .collect();
+        // A location without a registration is still part of the graph, so a missing one is skipped.
+        let found =
+            join_all(locations.difference(&registered).map(|location| {
+                management_client.fetch_registration_by_iceberg_location(location)
+            }))
+            .await
+            .into_iter()
+            .filter(|result| !matches!(result, Err(e) if ErrorKind::of(e) == ErrorKind::NotFound))
+            .collect();
+        registrations.extend(partition(found, &mut errors));
+        let known: BTreeSet<&FourByFour> = inventories
+            .iter()
+            .map(|inventory| &inventory.four_by_four.four_by_four)
+            .collect();
+        let siblings = join_all(
+            locations
+                .iter()
+                .map(|location| management_client.fetch_inventories_by_iceberg_location(location)),
+        )
+        .await;
+        let siblings: Vec<Inventory> = partition(siblings, &mut errors)
+            .into_iter()
+            .flatten()
+            .filter(|inventory| !known.contains(&inventory.four_by_four.four_by_four))
+            .collect();
+        let tables: BTreeSet<(IcebergLocation, String)> = inventories
+            .iter()
+            .chain(siblings.iter())
+            .map(|inventory| {
+                (
+                    inventory.id.iceberg_location.iceberg_location.clone(),
+                    inventory.id.schema_table.schema_table.clone(),
+                )
+            })
+            .chain(match &identifier {
+                Identifier::IcebergAsset {
+                    iceberg_location,
+                    schema_table,
+                }
+                | Identifier::Inventory {
+                    iceberg_location,
+                    schema_table,
+                    ..
+                } => Some((iceberg_location.clone(), schema_table.clone())),
+                _ => None,
+            })
+            .collect();
+        let tables = join_all(
+            tables
+                .into_iter()
+                .map(|(iceberg_location, schema_table)| async {
+                    ResolvedTable::resolve(
+                        dici_catalog,
+                        iceberg_location.clone(),
+                        schema_table.clone(),
+                    )
+                    .await
+                    .unwrap_or_else(|e| ResolvedTable::failed(iceberg_location, schema_table, &e))
+                }),
+        )
+        .await;
+        Ok(Self {
+            identifier,
+            inventories,
+            registrations,
+            siblings,
+            tables,
+            errors,
+        })
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::http::management::memory::InMemoryManagement;
+    use crate::api::store::catalog::config::{CatalogBackend, DiciConfig};
+    use iceberg::NamespaceIdent;
+    use serde_json::json;
+    use std::collections::HashMap;
+    const LOCATION: &str = "_0123456789abcdef0123456789abcdef";
+    const BUDGET: &str = "_fedcba9876543210fedcba9876543210";
+    fn inventory(domain: &str, location: &str, schema_table: &str, fxf: &str) -> Inventory {
+        serde_json::from_value(json!({
+            "id": {
+                "domain": {"domain": domain},
+                "icebergLocation": {"icebergLocation": location},
+                "schemaTable": {"schemaTable": schema_table}
+            },
+            "fourByFour": {"fourByFour": fxf},
+            "createdAt": "2024-01-01T00:00:00Z",
+            "updatedAt": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap()
+    }
+    fn parse(s: &str) -> Identifier {
+        s.parse().unwrap()
+    }
+    #[test]
+    fn detects_every_format() {
+        let location: IcebergLocation = LOCATION.parse().unwrap();
+        assert_eq!(
+            parse("abcd-1234"),
+            Identifier::FourByFour {
+                four_by_four: "abcd-1234".parse().unwrap()
+            }
+        );
+        assert_eq!(
+            parse(LOCATION),
+            Identifier::IcebergLocation {
+                iceberg_location: location.clone()
+            }
+        );
+        for asset in [
+            format!("{}/schema.table", LOCATION),
+            format!("{}.schema.table", LOCATION),
+        ] {
+            assert_eq!(
+                parse(&asset),
+                Identifier::IcebergAsset {
+                    iceberg_location: location.clone(),
+                    schema_table: "schema.table".to_string()
+                }
+            );
+        }
+        assert_eq!(
+            parse("data.example.gov"),
+            Identifier::Domain {
+                domain: "data.example.gov".parse().unwrap()
+            }
+        );
+        assert_eq!(
+            parse(&format!("data.example.gov/{}/schema.table", LOCATION)),
+            Identifier::Inventory {
+                domain: "data.example.gov".parse().unwrap(),
+                iceberg_location: location,
+                schema_table: "schema.table".to_string()
+            }
+        );
+        assert_eq!(
+            parse(" erp_pro_10/incode "),
+            Identifier::RegistrationPath {
+                path: "erp_pro_10/incode".parse().unwrap()
+            }
+        );
+    }
+    #[test]
+    fn prefixes_force_a_format() {
+        assert_eq!(
+            parse("reg:data.example.gov"),
+            Identifier::RegistrationPath {
+                path: "data.example.gov".parse().unwrap()
+            }
+        );
+        assert_eq!(
+            parse("core:abcd-1234"),
+            Identifier::FourByFour {
+                four_by_four: "abcd-1234".parse().unwrap()
+            }
+        );
+        assert_eq!(
+            parse(&format!("iceberg:{}/schema.table", LOCATION)),
+            Identifier::IcebergAsset {
+                iceberg_location: LOCATION.parse().unwrap(),
+                schema_table: "schema.table".to_string()
+            }
+        );
+    }
+    #[test]
+    fn rejects_unrecognized_identifiers() {
+        for s in ["", "a//b", "../etc", "core:ABCD-1234"] {
+            assert!(s.parse::<Identifier>().is_err(), "{} parsed", s);
+        }
+    }
+    #[tokio::test]
+    async fn a_table_the_catalog_fails_for_leaves_the_graph_partial() {
+        let management = InMemoryManagement::new(
+            vec![
+                inventory("data.example.gov", LOCATION, "incode.permits", "abcd-1234"),
+                inventory("data.example.gov", BUDGET, "finance.budget", "efgh-5678"),
+                inventory("other.example.gov", LOCATION, "incode.permits", "ijkl-9012"),
+            ],
+            vec![],
+        );
+        let config = DiciConfig::builder()
+            .warehouse("memory://warehouse")
+            .backend(CatalogBackend::Memory)
+            .build();
+        let dici_catalog = DiciCatalog::builder().config(config).build();
+        // Only the permits location exists, the catalog fails for budget.
+        dici_catalog
+            .catalog()
+            .await
+            .unwrap()
+            .create_namespace(&NamespaceIdent::new(LOCATION.to_string()), HashMap::new())
+            .await
+            .unwrap();
+        let graph = IdentityGraph::resolve(parse("data.example.gov"), &management, &dici_catalog)
+            .await
+            .unwrap();
+        assert_eq!(graph.inventories.len(), 2);
+        let siblings: Vec<&str> = graph
+            .siblings
+            .iter()
+            .map(|sibling| sibling.four_by_four.four_by_four.as_str())
+            .collect();
+        assert_eq!(siblings, ["ijkl-9012"]);
+        let tables: Vec<(&str, bool, bool)> = graph
+            .tables
+            .iter()
+            .map(|table| {
+                (
+                    table.schema_table.as_str(),
+                    table.exists,
+                    table.error.is_some(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            tables,
+            [
+                ("incode.permits", false, false),
+                ("finance.budget", false, true)
+            ]
+        );
+    }
+}
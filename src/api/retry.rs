@@ -0,0 +1,32 @@
+use std::time::Duration;
+// Attempts after the first one, waiting `backoff` before the first retry and doubling it each time.
+// Shared by the management client, bulk syncs and webhooks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retry {
+    pub retries: u32,
+    pub backoff: Duration,
+}
+impl Retry {
+    pub fn new(retries: u32, backoff: Duration) -> Self {
+        Self { retries, backoff }
+    }
+    // Runs `attempt` until `retryable` turns its outcome down or the retries are used up, the outcome
+    // comes back with the number of attempts made.
+    pub async fn run<T, F, Fut>(&self, mut attempt: F, retryable: impl Fn(&T) -> bool) -> (u32, T)
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let mut attempts = 0;
+        let mut backoff = self.backoff;
+        loop {
+            attempts += 1;
+            let outcome = attempt().await;
+            if attempts > self.retries || !retryable(&outcome) {
+                return (attempts, outcome);
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = backoff.saturating_mul(2);
+        }
+    }
+}
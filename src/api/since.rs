@@ -0,0 +1,86 @@
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Duration, Utc};
+// A duration such as 90s, 30m, 2h, 1d, 1w or a combination like 1h30m.
+pub fn parse_duration(s: &str) -> Result<Duration> {
+    let mut total = Duration::zero();
+    let mut rest = s.trim();
+    if rest.is_empty() {
+        bail!("Invalid duration '{}', expected e.g. 30m, 2h or 1d", s);
+    }
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let amount: i64 = rest[..digits]
+            .parse()
+            .with_context(|| format!("Invalid duration '{}', expected e.g. 30m, 2h or 1d", s))?;
+        rest = &rest[digits..];
+        let unit = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let duration = match &rest[..unit] {
+            "s" => Duration::try_seconds(amount),
+            "m" => Duration::try_minutes(amount),
+            "h" => Duration::try_hours(amount),
+            "d" => Duration::try_days(amount),
+            "w" => Duration::try_weeks(amount),
+            unit => bail!(
+                "Invalid duration unit '{}' in '{}', expected s, m, h, d or w",
+                unit,
+                s
+            ),
+        };
+        total = duration
+            .and_then(|duration| total.checked_add(&duration))
+            .with_context(|| format!("Duration '{}' is too long", s))?;
+        rest = &rest[unit..];
+    }
+    Ok(total)
+}
+// An RFC 3339 datetime, or a duration counted back from now such as 2h.
+pub fn parse_since(s: &str) -> Result<DateTime<Utc>> {
+    match s.parse::<DateTime<Utc>>() {
+        Ok(since) => Ok(since),
+        Err(_) => {
+            let duration = parse_duration(s).with_context(|| {
+                format!("Invalid since '{}', expected a datetime or a duration", s)
+            })?;
+            Utc::now()
+                .checked_sub_signed(duration)
+                .with_context(|| format!("Since '{}' is too long ago", s))
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn parses_units_and_combinations() {
+        assert_eq!(parse_duration("90s").unwrap(), Duration::seconds(90));
+        assert_eq!(parse_duration("30m").unwrap(), Duration::minutes(30));
+        assert_eq!(parse_duration(" 2h ").unwrap(), Duration::hours(2));
+        assert_eq!(parse_duration("1d").unwrap(), Duration::days(1));
+        assert_eq!(parse_duration("1w").unwrap(), Duration::weeks(1));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::minutes(90));
+    }
+    #[test]
+    fn rejects_malformed_durations() {
+        for s in ["", "h", "10", "10y", "1h30", "-1h"] {
+            assert!(parse_duration(s).is_err(), "{} parsed", s);
+        }
+    }
+    #[test]
+    fn rejects_overflowing_durations() {
+        assert!(parse_duration("9223372036854775807w").is_err());
+        assert!(parse_duration("99999999999999999999s").is_err());
+        assert!(parse_since("9223372036854775s").is_err());
+    }
+    #[test]
+    fn parses_datetimes_and_durations_back_from_now() {
+        let since = parse_since("2024-01-02T03:04:05Z").unwrap();
+        assert_eq!(since.to_rfc3339(), "2024-01-02T03:04:05+00:00");
+        let since = parse_since("2h").unwrap();
+        let ago = Utc::now() - since;
+        assert!(ago >= Duration::hours(2) && ago < Duration::hours(2) + Duration::minutes(1));
+    }
+}
@@ -0,0 +1,411 @@
+use anyhow::{Context, Result, bail};
+use arrow::array::{
+    Array, ArrayRef, AsArray, Int64Array, RecordBatch, StringArray, StructArray, new_null_array,
+};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Fields, Schema, SchemaRef};
+use datafusion::catalog::streaming::StreamingTable;
+use datafusion::error::DataFusionError;
+use datafusion::execution::{SendableRecordBatchStream, TaskContext};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::streaming::PartitionStream;
+use futures::{Stream, StreamExt, TryStreamExt};
+use iceberg::arrow::{ArrowFileReader, schema_to_arrow_schema};
+use iceberg::io::FileIO;
+use iceberg::spec::{DataContentType, ManifestContentType, ManifestStatus, Operation, SnapshotRef};
+use iceberg::table::Table;
+use parquet::arrow::{PARQUET_FIELD_ID_META_KEY, ParquetRecordBatchStreamBuilder};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+pub const CHANGE_TYPE_COLUMN: &str = "_change_type";
+pub const SNAPSHOT_ID_COLUMN: &str = "_snapshot_id";
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeType {
+    Added,
+    Deleted,
+}
+impl ChangeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeType::Added => "added",
+            ChangeType::Deleted => "deleted",
+        }
+    }
+}
+// What a single snapshot did, taken from its summary and manifest entries.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotChange {
+    pub snapshot_id: i64,
+    pub parent_snapshot_id: Option<i64>,
+    pub timestamp_ms: i64,
+    pub operation: String,
+    pub added_data_files: Vec<String>,
+    pub deleted_data_files: Vec<String>,
+    // Row level delete files can't be turned into rows here, so rows removed by merge-on-read deletes
+    // are missing from the changes and only these files are reported.
+    pub added_delete_files: Vec<String>,
+    pub summary: HashMap<String, String>,
+}
+// Rows added and deleted between two snapshots, the rows carry their change type and snapshot id. Only
+// whole data files count, rows removed by merge-on-read deletes are not among the deleted rows. The data
+// files are only read when the changes are queried.
+#[derive(Debug, Clone)]
+pub struct TableChanges {
+    pub from_snapshot_id: i64,
+    pub to_snapshot_id: i64,
+    pub snapshots: Vec<SnapshotChange>,
+    pub schema: SchemaRef,
+    files: Vec<ChangedFile>,
+}
+impl TableChanges {
+    // Changes made after `from` up to and including `to`, `to` defaults to the current snapshot.
+    pub async fn between(table: &Table, from: i64, to: Option<i64>) -> Result<Self> {
+        let metadata = table.metadata();
+        let to = match to {
+            Some(to) => to,
+            None => metadata
+                .current_snapshot_id()
+                .context("The table has no current snapshot")?,
+        };
+        let snapshots = Self::lineage(table, from, to)?;
+        let schema = Self::change_schema(table)?;
+        let field_ids: Arc<[i32]> = table
+            .metadata()
+            .current_schema()
+            .as_struct()
+            .fields()
+            .iter()
+            .map(|field| field.id)
+            .collect();
+        let mut changes = Vec::new();
+        let mut files = Vec::new();
+        for snapshot in snapshots {
+            let change = Self::snapshot_change(table, &snapshot).await?;
+            // Replace snapshots rewrite the same rows into new files, e.g. compaction, so they change nothing.
+            if snapshot.summary().operation == Operation::Replace {
+                changes.push(change);
+                continue;
+            }
+            for (paths, change_type) in [
+                (&change.added_data_files, ChangeType::Added),
+                (&change.deleted_data_files, ChangeType::Deleted),
+            ] {
+                files.extend(paths.iter().map(|path| ChangedFile {
+                    file_io: table.file_io().clone(),
+                    path: path.clone(),
+                    schema: schema.clone(),
+                    field_ids: field_ids.clone(),
+                    change_type,
+                    snapshot_id: change.snapshot_id,
+                }));
+            }
+            changes.push(change);
+        }
+        Ok(Self {
+            from_snapshot_id: from,
+            to_snapshot_id: to,
+            snapshots: changes,
+            schema,
+            files,
+        })
+    }
+    pub fn delete_files(&self) -> usize {
+        self.snapshots
+            .iter()
+            .map(|snapshot| snapshot.added_delete_files.len())
+            .sum()
+    }
+    // One partition per data file, each streamed from storage as the query reads it.
+    pub fn table_provider(&self) -> Result<StreamingTable> {
+        let partitions = self
+            .files
+            .iter()
+            .map(|file| Arc::new(file.clone()) as Arc<dyn PartitionStream>)
+            .collect();
+        StreamingTable::try_new(self.schema.clone(), partitions)
+            .context("Failed to create changes table")
+    }
+    // Snapshots after `from` up to `to`, oldest first.
+    fn lineage(table: &Table, from: i64, to: i64) -> Result<Vec<SnapshotRef>> {
+        let metadata = table.metadata();
+        metadata
+            .snapshot_by_id(from)
+            .with_context(|| format!("Snapshot {} does not exist", from))?;
+        let mut lineage = Vec::new();
+        let mut current = Some(to);
+        while let Some(snapshot_id) = current {
+            if snapshot_id == from {
+                lineage.reverse();
+                return Ok(lineage);
+            }
+            let snapshot = metadata
+                .snapshot_by_id(snapshot_id)
+                .with_context(|| format!("Snapshot {} does not exist", snapshot_id))?;
+            current = snapshot.parent_snapshot_id();
+            lineage.push(snapshot.clone());
+        }
+        bail!("Snapshot {} is not an ancestor of snapshot {}", from, to)
+    }
+    fn change_schema(table: &Table) -> Result<SchemaRef> {
+        let table_schema = schema_to_arrow_schema(table.metadata().current_schema())
+            .context("Failed to convert the table schema")?;
+        let mut fields: Vec<Field> = table_schema
+            .fields()
+            .iter()
+            .map(|field| {
+                field
+                    .as_ref()
+                    .clone()
+                    .with_metadata(HashMap::new())
+                    .with_nullable(true)
+            })
+            .collect();
+        fields.push(Field::new(CHANGE_TYPE_COLUMN, DataType::Utf8, false));
+        fields.push(Field::new(SNAPSHOT_ID_COLUMN, DataType::Int64, false));
+        Ok(Arc::new(Schema::new(fields)))
+    }
+    async fn snapshot_change(table: &Table, snapshot: &SnapshotRef) -> Result<SnapshotChange> {
+        let file_io = table.file_io();
+        let snapshot_id = snapshot.snapshot_id();
+        let manifest_list = snapshot
+            .load_manifest_list(file_io, table.metadata())
+            .await
+            .with_context(|| format!("Failed to load manifest list of snapshot {}", snapshot_id))?;
+        let mut change = SnapshotChange {
+            snapshot_id,
+            parent_snapshot_id: snapshot.parent_snapshot_id(),
+            timestamp_ms: snapshot.timestamp_ms(),
+            operation: format!("{:?}", snapshot.summary().operation).to_lowercase(),
+            added_data_files: vec![],
+            deleted_data_files: vec![],
+            added_delete_files: vec![],
+            summary: snapshot.summary().additional_properties.clone(),
+        };
+        // Entries added or deleted by a snapshot only live in manifests written by that snapshot.
+        for manifest_file in manifest_list
+            .entries()
+            .iter()
+            .filter(|manifest_file| manifest_file.added_snapshot_id == snapshot_id)
+        {
+            let manifest = manifest_file
+                .load_manifest(file_io)
+                .await
+                .with_context(|| {
+                    format!("Failed to load manifest {}", manifest_file.manifest_path)
+                })?;
+            for entry in manifest.entries() {
+                if entry.snapshot_id() != Some(snapshot_id) {
+                    continue;
+                }
+                let data_file = entry.data_file();
+                let path = data_file.file_path().to_string();
+                match (
+                    &manifest_file.content,
+                    data_file.content_type(),
+                    entry.status(),
+                ) {
+                    (ManifestContentType::Data, DataContentType::Data, ManifestStatus::Added) => {
+                        change.added_data_files.push(path)
+                    }
+                    (ManifestContentType::Data, DataContentType::Data, ManifestStatus::Deleted) => {
+                        change.deleted_data_files.push(path)
+                    }
+                    (ManifestContentType::Deletes, _, ManifestStatus::Added) => {
+                        change.added_delete_files.push(path)
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(change)
+    }
+}
+// A data file added or deleted by a snapshot.
+#[derive(Debug, Clone)]
+struct ChangedFile {
+    file_io: FileIO,
+    path: String,
+    schema: SchemaRef,
+    field_ids: Arc<[i32]>,
+    change_type: ChangeType,
+    snapshot_id: i64,
+}
+impl ChangedFile {
+    async fn read(self) -> Result<impl Stream<Item = Result<RecordBatch>>> {
+        let input = self.file_io.new_input(&self.path)?;
+        let metadata = input
+            .metadata()
+            .await
+            .with_context(|| format!("Failed to read data file {}", self.path))?;
+        let reader = input
+            .reader()
+            .await
+            .with_context(|| format!("Failed to read data file {}", self.path))?;
+        let batches = ParquetRecordBatchStreamBuilder::new(ArrowFileReader::new(metadata, reader))
+            .await
+            .with_context(|| format!("Failed to open data file {}", self.path))?
+            .build()?;
+        Ok(batches.map(move |batch| {
+            let batch = batch.with_context(|| format!("Failed to read data file {}", self.path))?;
+            self.project(&batch)
+        }))
+    }
+    // Lines a data file's batch up with the change schema by iceberg field id, so renamed columns are
+    // found under their old name and columns added since are null.
+    fn project(&self, batch: &RecordBatch) -> Result<RecordBatch> {
+        let rows = batch.num_rows();
+        let file_fields = batch.schema().fields().clone();
+        let mut columns: Vec<ArrayRef> = self
+            .schema
+            .fields()
+            .iter()
+            .zip(self.field_ids.iter())
+            .map(
+                |(field, id)| match position(&file_fields, Some(*id), field.name()) {
+                    Some(index) => align(batch.column(index), field),
+                    None => Ok(new_null_array(field.data_type(), rows)),
+                },
+            )
+            .collect::<Result<_>>()?;
+        columns.push(Arc::new(StringArray::from(vec![
+            self.change_type.as_str();
+            rows
+        ])));
+        columns.push(Arc::new(Int64Array::from(vec![self.snapshot_id; rows])));
+        RecordBatch::try_new(self.schema.clone(), columns).context("Failed to build changes batch")
+    }
+}
+impl PartitionStream for ChangedFile {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let batches = futures::stream::once(self.clone().read())
+            .try_flatten()
+            .map_err(|e| DataFusionError::External(e.into()));
+        Box::pin(RecordBatchStreamAdapter::new(self.schema.clone(), batches))
+    }
+}
+fn field_id(field: &Field) -> Option<i32> {
+    field
+        .metadata()
+        .get(PARQUET_FIELD_ID_META_KEY)?
+        .parse()
+        .ok()
+}
+// Files written without field ids are matched by name.
+fn position(fields: &Fields, id: Option<i32>, name: &str) -> Option<usize> {
+    match fields.iter().any(|field| field_id(field).is_some()) {
+        true => fields
+            .iter()
+            .position(|field| id.is_some() && field_id(field) == id),
+        false => fields.iter().position(|field| field.name() == name),
+    }
+}
+// Struct fields are lined up by field id like the columns. Lists and maps are cast as they are, so one
+// whose nested fields changed since the file was written is refused rather than read into the wrong fields.
+fn align(column: &ArrayRef, field: &Field) -> Result<ArrayRef> {
+    match (column.data_type(), field.data_type()) {
+        (DataType::Struct(file_fields), DataType::Struct(fields)) => {
+            let column = column.as_struct();
+            let children = fields
+                .iter()
+                .map(
+                    |child| match position(file_fields, field_id(child), child.name()) {
+                        Some(index) => align(column.column(index), child),
+                        None => Ok(new_null_array(child.data_type(), column.len())),
+                    },
+                )
+                .collect::<Result<_>>()?;
+            let column = StructArray::try_new(fields.clone(), children, column.nulls().cloned())
+                .with_context(|| format!("Failed to build column {}", field.name()))?;
+            Ok(Arc::new(column))
+        }
+        (file_type, data_type) if !same_shape(file_type, data_type) => bail!(
+            "Column {} changed its nested fields since the data file was written, its changes can't be read",
+            field.name()
+        ),
+        (_, data_type) => cast(column, data_type)
+            .with_context(|| format!("Failed to cast column {}", field.name())),
+    }
+}
+fn same_shape(file_type: &DataType, data_type: &DataType) -> bool {
+    let same_field = |file_field: &Field, field: &Field| {
+        (field_id(file_field).is_none() || field_id(file_field) == field_id(field))
+            && same_shape(file_field.data_type(), field.data_type())
+    };
+    match (file_type, data_type) {
+        (DataType::Struct(file_fields), DataType::Struct(fields)) => {
+            file_fields.len() == fields.len()
+                && file_fields.iter().zip(fields).all(|(file_field, field)| {
+                    file_field.name() == field.name() && same_field(file_field, field)
+                })
+        }
+        (DataType::List(file_field), DataType::List(field))
+        | (DataType::LargeList(file_field), DataType::LargeList(field))
+        | (DataType::FixedSizeList(file_field, _), DataType::FixedSizeList(field, _))
+        | (DataType::Map(file_field, _), DataType::Map(field, _)) => same_field(file_field, field),
+        _ => !file_type.is_nested() && !data_type.is_nested(),
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int32Array, ListArray};
+    use arrow::datatypes::Int32Type;
+    fn with_id(field: Field, id: i32) -> Field {
+        field.with_metadata(HashMap::from([(
+            PARQUET_FIELD_ID_META_KEY.to_string(),
+            id.to_string(),
+        )]))
+    }
+    #[test]
+    fn aligns_struct_fields_by_id() {
+        let written = StructArray::from(vec![(
+            Arc::new(with_id(Field::new("old_name", DataType::Int32, true), 2)),
+            Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef,
+        )]);
+        let field = Field::new_struct(
+            "point",
+            vec![
+                with_id(Field::new("added", DataType::Int64, true), 3),
+                with_id(Field::new("new_name", DataType::Int64, true), 2),
+            ],
+            true,
+        );
+        let column = align(&(Arc::new(written) as ArrayRef), &field).unwrap();
+        let column = column.as_struct();
+        assert_eq!(column.column(0).null_count(), 2);
+        assert_eq!(
+            column
+                .column(1)
+                .as_primitive::<arrow::datatypes::Int64Type>()
+                .values(),
+            &[1, 2]
+        );
+    }
+    #[test]
+    fn refuses_lists_whose_nested_fields_changed() {
+        let written: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1)]),
+        ]));
+        let field = Field::new_list(
+            "points",
+            Field::new_struct(
+                "element",
+                vec![Field::new("x", DataType::Int32, true)],
+                true,
+            ),
+            true,
+        );
+        assert!(align(&written, &field).is_err());
+        let field = Field::new_list("values", Field::new("item", DataType::Int64, true), true);
+        assert_eq!(
+            align(&written, &field).unwrap().data_type(),
+            field.data_type()
+        );
+    }
+}
@@ -1,11 +1,11 @@
+use crate::api::identifier::FourByFour;
 use crate::api::store::asset::traits::sqlable::TableReferenceSource;
 use anyhow::Result;
 use datafusion::common::TableReference;
 use typed_builder::TypedBuilder;
-pub type CoreFxf = String;
+pub type CoreFxf = FourByFour;
 #[derive(TypedBuilder, Clone)]
 pub struct CoreAsset {
-    #[builder(setter(into))]
     pub fxf: CoreFxf,
 }
 impl TableReferenceSource for CoreAsset {
@@ -1,26 +1,40 @@
-use crate::api::http::management::client::ManagementClient;
+use crate::api::http::management::api::{ManagementApi, default_management_api};
 use crate::api::store::asset::core::{CoreAsset, CoreFxf};
+use crate::api::store::asset::error::AssetParseError;
 use crate::api::store::asset::iceberg::{IcebergAsset, IcebergLocation, IcebergSchemaTable};
+use crate::api::store::asset::inventory::{InventoryAsset, InventoryDomain};
+use crate::api::store::asset::registration::{RegistrationAsset, RegistrationPath};
 use crate::api::store::asset::traits::sqlable::TableReferenceSource;
-use crate::api::store::asset::traits::table_source::TableIdentitySource;
+use crate::api::store::asset::traits::table_source::{TableIdentitySource, TableVersionSource};
+use crate::api::store::asset::version::TableVersion;
 use crate::api::store::catalog::catalog_source::CatalogSource;
 use crate::api::store::catalog::dici::DiciCatalog;
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use datafusion::common::TableReference;
-use iceberg::TableIdent;
-use iceberg_catalog_glue::GlueCatalog;
+use iceberg::table::Table;
+use iceberg::{Catalog, TableIdent};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::Arc;
 use typed_builder::TypedBuilder;
 #[derive(TypedBuilder, Clone)]
 pub struct CoreArgs {
     asset: CoreAsset,
     #[builder(default)]
     dici_catalog: DiciCatalog,
+    #[builder(default = default_management_api(), setter(into))]
+    management_client: Arc<dyn ManagementApi>,
     #[builder(default)]
-    management_client: ManagementClient,
+    version: TableVersion,
 }
-impl Into<DiciAsset> for CoreArgs {
-    fn into(self) -> DiciAsset {
-        DiciAsset::Core(self)
+impl CoreArgs {
+    pub fn asset(&self) -> &CoreAsset {
+        &self.asset
+    }
+}
+impl From<CoreArgs> for DiciAsset {
+    fn from(args: CoreArgs) -> Self {
+        DiciAsset::Core(args)
     }
 }
 #[derive(TypedBuilder, Clone)]
@@ -28,17 +42,69 @@ pub struct IcebergArgs {
     asset: IcebergAsset,
     #[builder(default)]
     dici_catalog: DiciCatalog,
+    #[builder(default)]
+    version: TableVersion,
+}
+impl IcebergArgs {
+    pub fn asset(&self) -> &IcebergAsset {
+        &self.asset
+    }
+}
+impl From<IcebergArgs> for DiciAsset {
+    fn from(args: IcebergArgs) -> Self {
+        DiciAsset::Iceberg(args)
+    }
+}
+#[derive(TypedBuilder, Clone)]
+pub struct InventoryArgs {
+    asset: InventoryAsset,
+    #[builder(default)]
+    dici_catalog: DiciCatalog,
+    #[builder(default = default_management_api(), setter(into))]
+    management_client: Arc<dyn ManagementApi>,
+    #[builder(default)]
+    version: TableVersion,
+}
+impl InventoryArgs {
+    pub fn asset(&self) -> &InventoryAsset {
+        &self.asset
+    }
 }
-impl Into<DiciAsset> for IcebergArgs {
-    fn into(self) -> DiciAsset {
-        DiciAsset::Iceberg(self)
+impl From<InventoryArgs> for DiciAsset {
+    fn from(args: InventoryArgs) -> Self {
+        DiciAsset::Inventory(args)
     }
 }
+#[derive(TypedBuilder, Clone)]
+pub struct RegistrationArgs {
+    asset: RegistrationAsset,
+    #[builder(default)]
+    dici_catalog: DiciCatalog,
+    #[builder(default = default_management_api(), setter(into))]
+    management_client: Arc<dyn ManagementApi>,
+    #[builder(default)]
+    version: TableVersion,
+}
+impl RegistrationArgs {
+    pub fn asset(&self) -> &RegistrationAsset {
+        &self.asset
+    }
+}
+impl From<RegistrationArgs> for DiciAsset {
+    fn from(args: RegistrationArgs) -> Self {
+        DiciAsset::Registration(args)
+    }
+}
+#[derive(Clone)]
 pub enum DiciAsset {
     Core(CoreArgs),
     Iceberg(IcebergArgs),
+    Inventory(InventoryArgs),
+    Registration(RegistrationArgs),
 }
 impl DiciAsset {
+    // These build a fresh catalog and management client per asset, nothing is cached between them. Use
+    // DiciContext::core_asset and friends, or with_clients, to share one catalog and its table cache.
     pub fn core(fxf: CoreFxf) -> Self {
         CoreArgs::builder()
             .asset(CoreAsset::builder().fxf(fxf).build())
@@ -59,14 +125,151 @@ impl DiciAsset {
             .build()
             .into()
     }
-}
-impl CatalogSource for DiciAsset {
-    fn catalog(&self) -> impl Future<Output = Result<GlueCatalog>> {
-        let dici_catalog = match self {
+    pub fn inventory(
+        domain: InventoryDomain,
+        iceberg_location: IcebergLocation,
+        iceberg_schema_table: IcebergSchemaTable,
+    ) -> Self {
+        InventoryArgs::builder()
+            .asset(
+                InventoryAsset::builder()
+                    .domain(domain)
+                    .location(iceberg_location)
+                    .schema_table(iceberg_schema_table)
+                    .build(),
+            )
+            .build()
+            .into()
+    }
+    pub fn registration(path: RegistrationPath, iceberg_schema_table: IcebergSchemaTable) -> Self {
+        RegistrationArgs::builder()
+            .asset(
+                RegistrationAsset::builder()
+                    .path(path)
+                    .schema_table(iceberg_schema_table)
+                    .build(),
+            )
+            .build()
+            .into()
+    }
+    // Reads the asset at the given version instead of its current snapshot.
+    pub fn with_version(mut self, version: TableVersion) -> Self {
+        match &mut self {
+            DiciAsset::Core(args) => args.version = version,
+            DiciAsset::Iceberg(args) => args.version = version,
+            DiciAsset::Inventory(args) => args.version = version,
+            DiciAsset::Registration(args) => args.version = version,
+        }
+        self
+    }
+    // Resolves the asset through the given clients instead of the ones it was built with.
+    pub fn with_clients(
+        mut self,
+        dici_catalog: &DiciCatalog,
+        management_client: &Arc<dyn ManagementApi>,
+    ) -> Self {
+        match &mut self {
+            DiciAsset::Core(args) => {
+                args.dici_catalog = dici_catalog.clone();
+                args.management_client = management_client.clone();
+            }
+            DiciAsset::Iceberg(args) => args.dici_catalog = dici_catalog.clone(),
+            DiciAsset::Inventory(args) => {
+                args.dici_catalog = dici_catalog.clone();
+                args.management_client = management_client.clone();
+            }
+            DiciAsset::Registration(args) => {
+                args.dici_catalog = dici_catalog.clone();
+                args.management_client = management_client.clone();
+            }
+        }
+        self
+    }
+    fn dici_catalog(&self) -> &DiciCatalog {
+        match self {
             DiciAsset::Core(CoreArgs { dici_catalog, .. }) => dici_catalog,
             DiciAsset::Iceberg(IcebergArgs { dici_catalog, .. }) => dici_catalog,
+            DiciAsset::Inventory(InventoryArgs { dici_catalog, .. }) => dici_catalog,
+            DiciAsset::Registration(RegistrationArgs { dici_catalog, .. }) => dici_catalog,
+        }
+    }
+}
+// core:<fxf>, iceberg:<icebergLocation>/<schemaTable>, inv:<domain>/<icebergLocation>/<schemaTable> or reg:<path>#<schemaTable>
+impl FromStr for DiciAsset {
+    type Err = AssetParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = |scheme: &'static str, expected: &'static str| AssetParseError::Malformed {
+            scheme,
+            asset: s.to_string(),
+            expected,
         };
-        dici_catalog.catalog()
+        let non_empty = |parts: &[&str]| parts.iter().all(|part| !part.is_empty());
+        let (scheme, rest) = s
+            .split_once(':')
+            .ok_or_else(|| AssetParseError::UnknownScheme(s.to_string()))?;
+        match scheme {
+            "core" => match rest {
+                "" => Err(malformed("core", "core:<fxf>")),
+                fxf => Ok(Self::core(fxf.parse()?)),
+            },
+            "iceberg" => match rest.split_once('/') {
+                Some((location, schema_table)) if non_empty(&[location, schema_table]) => {
+                    Ok(Self::iceberg(location.parse()?, schema_table.to_string()))
+                }
+                _ => Err(malformed(
+                    "iceberg",
+                    "iceberg:<icebergLocation>/<schemaTable>",
+                )),
+            },
+            "inv" => match rest.splitn(3, '/').collect::<Vec<_>>().as_slice() {
+                [domain, location, schema_table]
+                    if non_empty(&[domain, location, schema_table]) =>
+                {
+                    Ok(Self::inventory(
+                        domain.parse()?,
+                        location.parse()?,
+                        schema_table.to_string(),
+                    ))
+                }
+                _ => Err(malformed(
+                    "inv",
+                    "inv:<domain>/<icebergLocation>/<schemaTable>",
+                )),
+            },
+            "reg" => match rest.rsplit_once('#') {
+                Some((path, schema_table)) if non_empty(&[path, schema_table]) => {
+                    Ok(Self::registration(path.parse()?, schema_table.to_string()))
+                }
+                _ => Err(malformed("reg", "reg:<path>#<schemaTable>")),
+            },
+            _ => Err(AssetParseError::UnknownScheme(s.to_string())),
+        }
+    }
+}
+impl Display for DiciAsset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiciAsset::Core(CoreArgs { asset, .. }) => write!(f, "core:{}", asset.fxf),
+            DiciAsset::Iceberg(IcebergArgs { asset, .. }) => {
+                write!(f, "iceberg:{}/{}", asset.location, asset.schema_table)
+            }
+            DiciAsset::Inventory(InventoryArgs { asset, .. }) => write!(
+                f,
+                "inv:{}/{}/{}",
+                asset.domain, asset.location, asset.schema_table
+            ),
+            DiciAsset::Registration(RegistrationArgs { asset, .. }) => {
+                write!(f, "reg:{}#{}", asset.path, asset.schema_table)
+            }
+        }
+    }
+}
+impl CatalogSource for DiciAsset {
+    fn catalog(&self) -> impl Future<Output = Result<Arc<dyn Catalog>>> {
+        self.dici_catalog().catalog()
+    }
+    fn load_table(&self, ident: &TableIdent) -> impl Future<Output = Result<Table>> {
+        self.dici_catalog().load_table(ident)
     }
 }
 impl TableReferenceSource for DiciAsset {
@@ -74,6 +277,20 @@ impl TableReferenceSource for DiciAsset {
         match self {
             DiciAsset::Core(CoreArgs { asset, .. }) => asset.table_reference().await,
             DiciAsset::Iceberg(IcebergArgs { asset, .. }) => asset.table_reference().await,
+            DiciAsset::Inventory(InventoryArgs { asset, .. }) => asset.table_reference().await,
+            DiciAsset::Registration(RegistrationArgs { asset, .. }) => {
+                asset.table_reference().await
+            }
+        }
+    }
+}
+impl TableVersionSource for DiciAsset {
+    fn table_version(&self) -> &TableVersion {
+        match self {
+            DiciAsset::Core(CoreArgs { version, .. }) => version,
+            DiciAsset::Iceberg(IcebergArgs { version, .. }) => version,
+            DiciAsset::Inventory(InventoryArgs { version, .. }) => version,
+            DiciAsset::Registration(RegistrationArgs { version, .. }) => version,
         }
     }
 }
@@ -83,17 +300,22 @@ impl TableIdentitySource for DiciAsset {
             DiciAsset::Core(CoreArgs {
                 asset: CoreAsset { fxf },
                 management_client,
+                dici_catalog,
                 ..
             }) => {
-                let inventory = management_client
-                    .fetch_inventory_by_fxf(fxf.into())
+                dici_catalog
+                    .resolve_ident(&self.to_string(), async {
+                        let inventory = management_client
+                            .fetch_inventory_by_fxf(fxf)
+                            .await
+                            .context("Could not fetch inventory")?;
+                        TableIdent::from_strs([
+                            inventory.id.iceberg_location.iceberg_location.as_str(),
+                            inventory.id.schema_table.schema_table.as_str(),
+                        ])
+                        .context("Failed to parse table ident from core asset")
+                    })
                     .await
-                    .context("Could not fetch inventory")?;
-                TableIdent::from_strs([
-                    inventory.id.iceberg_location.iceberg_location,
-                    inventory.id.schema_table.schema_table,
-                ])
-                .context("Failed to parse table ident from core asset")
             }
             DiciAsset::Iceberg(IcebergArgs {
                 asset:
@@ -102,8 +324,69 @@ impl TableIdentitySource for DiciAsset {
                         schema_table,
                     },
                 ..
-            }) => TableIdent::from_strs([location, schema_table])
+            }) => TableIdent::from_strs([location.as_str(), schema_table.as_str()])
                 .context("Failed to parse table ident from iceberg asset"),
+            DiciAsset::Inventory(InventoryArgs {
+                asset:
+                    InventoryAsset {
+                        domain,
+                        location,
+                        schema_table,
+                    },
+                management_client,
+                dici_catalog,
+                ..
+            }) => {
+                dici_catalog
+                    .resolve_ident(&self.to_string(), async {
+                        let inventory = management_client
+                            .fetch_inventory_by_id(domain, location, schema_table)
+                            .await
+                            .context("Could not fetch inventory")?;
+                        TableIdent::from_strs([
+                            inventory.id.iceberg_location.iceberg_location.as_str(),
+                            inventory.id.schema_table.schema_table.as_str(),
+                        ])
+                        .context("Failed to parse table ident from inventory asset")
+                    })
+                    .await
+            }
+            DiciAsset::Registration(RegistrationArgs {
+                asset: RegistrationAsset { path, schema_table },
+                management_client,
+                dici_catalog,
+                ..
+            }) => {
+                dici_catalog
+                    .resolve_ident(&self.to_string(), async {
+                        let registrations = management_client
+                            .fetch_registrations_by_path(path)
+                            .await
+                            .context("Could not fetch registrations")?;
+                        // The path query may match more than one registration, prefer the exact path.
+                        let registration = match registrations
+                            .iter()
+                            .find(|registration| &registration.id.path == path)
+                        {
+                            Some(registration) => registration,
+                            None => match registrations.as_slice() {
+                                [registration] => registration,
+                                [] => bail!("Registration not found for path {}", path),
+                                _ => bail!(
+                                    "Path {} matches {} registrations, expected one",
+                                    path,
+                                    registrations.len()
+                                ),
+                            },
+                        };
+                        TableIdent::from_strs([
+                            registration.iceberg_location.iceberg_location.as_str(),
+                            schema_table.as_str(),
+                        ])
+                        .context("Failed to parse table ident from registration asset")
+                    })
+                    .await
+            }
         }
     }
 }
@@ -0,0 +1,18 @@
+use crate::api::identifier::IdentifierError;
+use thiserror::Error;
+#[derive(Error, Debug)]
+pub enum AssetParseError {
+    #[error(
+        "Invalid asset '{0}', expected core:<fxf>, iceberg:<icebergLocation>/<schemaTable>, \
+         inv:<domain>/<icebergLocation>/<schemaTable> or reg:<path>#<schemaTable>"
+    )]
+    UnknownScheme(String),
+    #[error("Invalid {scheme} asset '{asset}', expected {expected}")]
+    Malformed {
+        scheme: &'static str,
+        asset: String,
+        expected: &'static str,
+    },
+    #[error(transparent)]
+    Identifier(#[from] IdentifierError),
+}
@@ -1,11 +1,10 @@
+pub use crate::api::identifier::IcebergLocation;
 use crate::api::store::asset::traits::sqlable::TableReferenceSource;
 use datafusion::common::TableReference;
 use typed_builder::TypedBuilder;
-pub type IcebergLocation = String;
 pub type IcebergSchemaTable = String;
 #[derive(TypedBuilder, Clone)]
 pub struct IcebergAsset {
-    #[builder(setter(into))]
     pub location: IcebergLocation,
     #[builder(setter(into))]
     pub schema_table: IcebergSchemaTable,
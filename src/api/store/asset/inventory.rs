@@ -0,0 +1,21 @@
+use crate::api::identifier::Domain;
+use crate::api::store::asset::iceberg::{IcebergLocation, IcebergSchemaTable};
+use crate::api::store::asset::traits::sqlable::TableReferenceSource;
+use datafusion::common::TableReference;
+use typed_builder::TypedBuilder;
+pub type InventoryDomain = Domain;
+// An inventory identified by its id triple, resolved to a table through dici management.
+#[derive(TypedBuilder, Clone)]
+pub struct InventoryAsset {
+    pub domain: InventoryDomain,
+    pub location: IcebergLocation,
+    #[builder(setter(into))]
+    pub schema_table: IcebergSchemaTable,
+}
+impl TableReferenceSource for InventoryAsset {
+    async fn table_reference(&self) -> anyhow::Result<TableReference> {
+        Ok(TableReference::Bare {
+            table: self.schema_table.as_str().into(),
+        })
+    }
+}
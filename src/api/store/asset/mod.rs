@@ -1,4 +1,9 @@
+pub mod changes;
 pub mod core;
 pub mod dici;
+pub mod error;
 pub mod iceberg;
+pub mod inventory;
+pub mod registration;
 pub mod traits;
+pub mod version;
@@ -0,0 +1,19 @@
+pub use crate::api::identifier::RegistrationPath;
+use crate::api::store::asset::iceberg::IcebergSchemaTable;
+use crate::api::store::asset::traits::sqlable::TableReferenceSource;
+use datafusion::common::TableReference;
+use typed_builder::TypedBuilder;
+// A table of a registration, the registration path resolves to its icebergLocation through dici management.
+#[derive(TypedBuilder, Clone)]
+pub struct RegistrationAsset {
+    pub path: RegistrationPath,
+    #[builder(setter(into))]
+    pub schema_table: IcebergSchemaTable,
+}
+impl TableReferenceSource for RegistrationAsset {
+    async fn table_reference(&self) -> anyhow::Result<TableReference> {
+        Ok(TableReference::Bare {
+            table: self.schema_table.as_str().into(),
+        })
+    }
+}
@@ -0,0 +1,14 @@
+use crate::api::store::asset::changes::TableChanges;
+use crate::api::store::asset::traits::table_source::TableSource;
+use anyhow::Result;
+pub trait ChangesSource: TableSource {
+    fn changes(&self, from: i64, to: Option<i64>) -> impl Future<Output = Result<TableChanges>>;
+}
+impl<T> ChangesSource for T
+where
+    T: TableSource,
+{
+    async fn changes(&self, from: i64, to: Option<i64>) -> Result<TableChanges> {
+        TableChanges::between(&self.table().await?, from, to).await
+    }
+}
@@ -1,11 +1,9 @@
 use crate::api::store::asset::traits::table_source::TableSource;
+use crate::api::store::session::DiciSession;
 use anyhow::{Context, Result};
 use datafusion::dataframe::DataFrame;
 use datafusion::prelude::{SQLOptions, SessionContext};
 use datafusion::sql::TableReference;
-use iceberg::table::Table;
-use iceberg_datafusion::IcebergTableProvider;
-use std::sync::Arc;
 // Meaning we need to provide the table reference (not to be confused with the true table identity). For example, allowing the use of "this" for the table name.
 pub trait ManuallySqlAble: TableSource {
     fn context_with_table_reference(
@@ -32,12 +30,9 @@ where
         &self,
         table_reference: TableReference,
     ) -> Result<SessionContext> {
-        let table: Table = self.table().await?;
-        let ctx = SessionContext::new();
-        let table_provider = Arc::new(IcebergTableProvider::try_new_from_table(table).await?);
-        ctx.register_table(table_reference, table_provider)
-            .context("Failed to register table")?;
-        Ok(ctx)
+        let session = DiciSession::default();
+        session.register(table_reference, self).await?;
+        Ok(session.into_context())
     }
     async fn sql_with_table_reference(
         &self,
@@ -1,3 +1,4 @@
+pub mod changes_source;
 pub mod manually_sqlable;
 pub mod schema_source;
 pub mod sqlable;
@@ -1,11 +1,9 @@
 use crate::api::store::asset::traits::table_source::TableSource;
+use crate::api::store::session::DiciSession;
 use anyhow::{Context, Result};
 use datafusion::common::TableReference;
 use datafusion::dataframe::DataFrame;
 use datafusion::prelude::{SQLOptions, SessionContext};
-use iceberg::table::Table;
-use iceberg_datafusion::IcebergTableProvider;
-use std::sync::Arc;
 pub trait TableReferenceSource {
     fn table_reference(&self) -> impl Future<Output = Result<TableReference>>;
 }
@@ -23,17 +21,16 @@ where
     T: TableSource + TableReferenceSource,
 {
     async fn context(&self) -> Result<SessionContext> {
-        let table: Table = self.table().await?;
-        let ctx = SessionContext::new();
-        let table_provider = Arc::new(IcebergTableProvider::try_new_from_table(table).await?);
-        ctx.register_table(
-            self.table_reference()
-                .await
-                .context("Failed to get table reference")?,
-            table_provider,
-        )
-        .context("Failed to register table")?;
-        Ok(ctx)
+        let session = DiciSession::default();
+        session
+            .register(
+                self.table_reference()
+                    .await
+                    .context("Failed to get table reference")?,
+                self,
+            )
+            .await?;
+        Ok(session.into_context())
     }
     async fn sql(&self, sql: &str) -> Result<DataFrame> {
         self.context()
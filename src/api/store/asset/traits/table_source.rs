@@ -1,28 +1,41 @@
+use crate::api::store::asset::version::TableVersion;
 use crate::api::store::catalog::catalog_source::CatalogSource;
 use anyhow::{Context, Result};
+use iceberg::TableIdent;
 use iceberg::table::Table;
-use iceberg::{Catalog, TableIdent};
+use iceberg_datafusion::IcebergTableProvider;
 pub trait TableIdentitySource {
     fn table_ident(&self) -> impl Future<Output = Result<TableIdent>>;
 }
-pub trait TableSource: TableIdentitySource + CatalogSource {
+pub trait TableVersionSource {
+    fn table_version(&self) -> &TableVersion;
+}
+pub trait TableSource: TableIdentitySource + CatalogSource + TableVersionSource {
     fn table(&self) -> impl Future<Output = Result<Table>>;
+    // A provider reading the table at its requested version.
+    fn table_provider(&self) -> impl Future<Output = Result<IcebergTableProvider>>;
 }
 impl<T> TableSource for T
 where
-    T: TableIdentitySource + CatalogSource,
+    T: TableIdentitySource + CatalogSource + TableVersionSource,
 {
     async fn table(&self) -> Result<Table> {
-        self.catalog()
-            .await
-            .context("Failed to construct catalog")?
-            .load_table(
-                &self
-                    .table_ident()
-                    .await
-                    .context("Failed to construct table ident")?,
-            )
-            .await
-            .context("Failed to load table")
+        self.load_table(
+            &self
+                .table_ident()
+                .await
+                .context("Failed to construct table ident")?,
+        )
+        .await
+    }
+    async fn table_provider(&self) -> Result<IcebergTableProvider> {
+        let table = self.table().await?;
+        let provider = match self.table_version().snapshot_id(&table)? {
+            Some(snapshot_id) => {
+                IcebergTableProvider::try_new_from_table_snapshot(table, snapshot_id).await
+            }
+            None => IcebergTableProvider::try_new_from_table(table).await,
+        };
+        provider.context("Failed to create table provider")
     }
 }
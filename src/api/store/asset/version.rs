@@ -0,0 +1,50 @@
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use iceberg::table::Table;
+// Which snapshot of a table to read, defaults to the current one.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum TableVersion {
+    #[default]
+    Current,
+    Snapshot(i64),
+    AsOf(DateTime<Utc>),
+    Ref(String),
+}
+impl TableVersion {
+    // At most one of the options may be given, none means the current snapshot.
+    pub fn from_options(
+        snapshot: Option<i64>,
+        as_of: Option<DateTime<Utc>>,
+        reference: Option<String>,
+    ) -> Result<Self> {
+        match (snapshot, as_of, reference) {
+            (None, None, None) => Ok(Self::Current),
+            (Some(snapshot), None, None) => Ok(Self::Snapshot(snapshot)),
+            (None, Some(as_of), None) => Ok(Self::AsOf(as_of)),
+            (None, None, Some(reference)) => Ok(Self::Ref(reference)),
+            _ => bail!("Only one of snapshot, as-of or ref can be given"),
+        }
+    }
+    // The snapshot id to read, None when the table should be read as it currently is.
+    pub fn snapshot_id(&self, table: &Table) -> Result<Option<i64>> {
+        let metadata = table.metadata();
+        match self {
+            TableVersion::Current => Ok(None),
+            TableVersion::Snapshot(snapshot) => metadata
+                .snapshot_by_id(*snapshot)
+                .map(|snapshot| Some(snapshot.snapshot_id()))
+                .with_context(|| format!("Snapshot {} does not exist", snapshot)),
+            TableVersion::AsOf(as_of) => metadata
+                .history()
+                .iter()
+                .filter(|entry| entry.timestamp_ms <= as_of.timestamp_millis())
+                .max_by_key(|entry| entry.timestamp_ms)
+                .map(|entry| Some(entry.snapshot_id))
+                .with_context(|| format!("No snapshot exists as of {}", as_of.to_rfc3339())),
+            TableVersion::Ref(reference) => metadata
+                .snapshot_for_ref(reference)
+                .map(|snapshot| Some(snapshot.snapshot_id()))
+                .with_context(|| format!("Ref {} does not exist", reference)),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::api::http::management::api::ManagementApi;
+use crate::api::http::management::model::inventory::Inventory;
+use crate::api::identifier::IcebergLocation;
+use crate::api::store::catalog::catalog_source::CatalogSource;
+use anyhow::{Context, Result};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use iceberg::{NamespaceIdent, TableIdent};
+use serde::Serialize;
+// Inventory lookups in flight at once, a namespace can hold thousands of tables.
+const INVENTORY_CONCURRENCY: usize = 16;
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogTable {
+    pub iceberg_location: String,
+    pub schema_table: String,
+    pub inventories: Vec<Inventory>,
+}
+pub trait CatalogBrowser: CatalogSource {
+    fn namespaces(&self) -> impl Future<Output = Result<Vec<String>>>;
+    fn tables(&self, namespace: &str) -> impl Future<Output = Result<Vec<TableIdent>>>;
+    fn tables_with_inventories(
+        &self,
+        namespace: &str,
+        management_client: &dyn ManagementApi,
+    ) -> impl Future<Output = Result<Vec<CatalogTable>>>;
+}
+impl<T> CatalogBrowser for T
+where
+    T: CatalogSource,
+{
+    async fn namespaces(&self) -> Result<Vec<String>> {
+        Ok(self
+            .catalog()
+            .await
+            .context("Failed to construct catalog")?
+            .list_namespaces(None)
+            .await
+            .context("Failed to list namespaces")?
+            .into_iter()
+            .map(|namespace| namespace.inner().join("."))
+            .collect())
+    }
+    async fn tables(&self, namespace: &str) -> Result<Vec<TableIdent>> {
+        let namespace =
+            NamespaceIdent::from_strs(namespace.split('.')).context("Failed to parse namespace")?;
+        self.catalog()
+            .await
+            .context("Failed to construct catalog")?
+            .list_tables(&namespace)
+            .await
+            .context("Failed to list tables")
+    }
+    async fn tables_with_inventories(
+        &self,
+        namespace: &str,
+        management_client: &dyn ManagementApi,
+    ) -> Result<Vec<CatalogTable>> {
+        let tables = self.tables(namespace).await?;
+        stream::iter(tables.into_iter().map(|table| async move {
+            let iceberg_location = table.namespace().clone().inner().join(".");
+            let schema_table = table.name().to_string();
+            // Only namespaces named like an icebergLocation can have inventories.
+            let inventories = match iceberg_location.parse::<IcebergLocation>() {
+                Ok(location) => management_client
+                    .fetch_inventories_by_iceberg_location_and_table(&location, &schema_table)
+                    .await
+                    .context("Could not fetch inventories")?,
+                Err(_) => vec![],
+            };
+            Ok::<_, anyhow::Error>(CatalogTable {
+                iceberg_location,
+                schema_table,
+                inventories,
+            })
+        }))
+        .buffered(INVENTORY_CONCURRENCY)
+        .try_collect()
+        .await
+    }
+}
@@ -0,0 +1,99 @@
+use crate::api::lock::lock;
+use iceberg::TableIdent;
+use iceberg::table::Table;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+// Entries kept per map, the one loaded longest ago makes room for a new one.
+const MAX_ENTRIES: usize = 256;
+struct Cached<T> {
+    value: T,
+    loaded_at: Instant,
+}
+impl<T: Clone> Cached<T> {
+    fn new(value: T) -> Self {
+        Self {
+            value,
+            loaded_at: Instant::now(),
+        }
+    }
+    fn fresh(&self, ttl: Duration) -> Option<T> {
+        (self.loaded_at.elapsed() < ttl).then(|| self.value.clone())
+    }
+}
+fn insert_bounded<K: Eq + Hash + Clone, T: Clone>(
+    entries: &mut HashMap<K, Cached<T>>,
+    key: &K,
+    value: T,
+) {
+    if entries.len() >= MAX_ENTRIES && !entries.contains_key(key) {
+        let oldest = entries
+            .iter()
+            .min_by_key(|(_, cached)| cached.loaded_at)
+            .map(|(key, _)| key.clone());
+        if let Some(oldest) = oldest {
+            entries.remove(&oldest);
+        }
+    }
+    entries.insert(key.clone(), Cached::new(value));
+}
+// Loaded tables and resolved table idents, each served while younger than the ttl it is asked with.
+#[derive(Default)]
+pub struct TableCache {
+    tables: Mutex<HashMap<TableIdent, Cached<Table>>>,
+    idents: Mutex<HashMap<String, Cached<TableIdent>>>,
+}
+impl TableCache {
+    pub fn table(&self, ident: &TableIdent, ttl: Duration) -> Option<Table> {
+        lock(&self.tables)
+            .get(ident)
+            .and_then(|cached| cached.fresh(ttl))
+    }
+    // Keeps the previously cached table (and its manifest cache) while the metadata location is unchanged,
+    // and replaces it once the location moves on. Either way it is fresh again.
+    pub fn store_table(&self, ident: &TableIdent, table: Table) -> Table {
+        let mut tables = lock(&self.tables);
+        let table = match tables.get(ident) {
+            Some(cached) if cached.value.metadata_location() == table.metadata_location() => {
+                cached.value.clone()
+            }
+            _ => table,
+        };
+        insert_bounded(&mut tables, ident, table.clone());
+        table
+    }
+    pub fn ident(&self, key: &str, ttl: Duration) -> Option<TableIdent> {
+        lock(&self.idents)
+            .get(key)
+            .and_then(|cached| cached.fresh(ttl))
+    }
+    pub fn store_ident(&self, key: &str, ident: TableIdent) {
+        insert_bounded(&mut lock(&self.idents), &key.to_string(), ident);
+    }
+    pub fn invalidate(&self, ident: &TableIdent) {
+        lock(&self.tables).remove(ident);
+        lock(&self.idents).retain(|_, cached| &cached.value != ident);
+    }
+    pub fn clear(&self) {
+        lock(&self.tables).clear();
+        lock(&self.idents).clear();
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn evicts_the_oldest_ident_once_full() {
+        let cache = TableCache::default();
+        let ttl = Duration::from_secs(60);
+        for i in 0..=MAX_ENTRIES {
+            let ident = TableIdent::from_strs(["ns", &format!("t{i}")]).unwrap();
+            cache.store_ident(&format!("k{i}"), ident);
+        }
+        assert!(cache.ident("k0", ttl).is_none());
+        assert!(cache.ident("k1", ttl).is_some());
+        assert!(cache.ident(&format!("k{MAX_ENTRIES}"), ttl).is_some());
+        assert!(cache.ident("k1", Duration::ZERO).is_none());
+    }
+}
@@ -1,4 +1,17 @@
-use iceberg_catalog_glue::GlueCatalog;
+use anyhow::Context;
+use iceberg::table::Table;
+use iceberg::{Catalog, TableIdent};
+use std::sync::Arc;
 pub trait CatalogSource {
-    fn catalog(&self) -> impl Future<Output = anyhow::Result<GlueCatalog>>;
+    fn catalog(&self) -> impl Future<Output = anyhow::Result<Arc<dyn Catalog>>>;
+    fn load_table(&self, ident: &TableIdent) -> impl Future<Output = anyhow::Result<Table>> {
+        async move {
+            self.catalog()
+                .await
+                .context("Failed to construct catalog")?
+                .load_table(ident)
+                .await
+                .context("Failed to load table")
+        }
+    }
 }
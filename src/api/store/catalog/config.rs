@@ -1,22 +1,182 @@
-use anyhow::Context;
+use crate::api::config::error::ConfigError;
+use iceberg::io::{S3_ENDPOINT, S3_PATH_STYLE_ACCESS, S3_REGION};
+use iceberg_catalog_glue::{AWS_PROFILE_NAME, AWS_REGION_NAME};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
+use std::time::Duration;
 use typed_builder::TypedBuilder;
-fn warehouse_from_env() -> anyhow::Result<String> {
-    env::var("DICI_WAREHOUSE").context("DICI_WAREHOUSE is not set")
+const DEFAULT_TABLE_CACHE_TTL: Duration = Duration::from_secs(60);
+pub(crate) fn warehouse_from_env() -> Option<Warehouse> {
+    env::var("DICI_WAREHOUSE").ok()
 }
-fn default_warehouse() -> String {
-    warehouse_from_env()
-        .context("Could not determine warehouse")
-        .unwrap()
+fn catalog_uri_from_env() -> Option<CatalogUri> {
+    env::var("DICI_CATALOG_URI").ok()
+}
+pub(crate) fn backend_from_env() -> Result<Option<CatalogBackend>, ConfigError> {
+    env::var("DICI_CATALOG")
+        .ok()
+        .map(|kind| CatalogBackend::from_kind(&kind, catalog_uri_from_env()))
+        .transpose()
+}
+pub(crate) fn glue_options_from_env() -> GlueOptions {
+    GlueOptions {
+        region: env::var("DICI_GLUE_REGION").ok(),
+        endpoint: env::var("DICI_GLUE_ENDPOINT").ok(),
+        profile: env::var("DICI_AWS_PROFILE").ok(),
+    }
+}
+pub(crate) fn s3_options_from_env() -> Result<S3Options, ConfigError> {
+    let path_style_access = env::var("DICI_S3_PATH_STYLE_ACCESS")
+        .ok()
+        .map(|value| {
+            value.parse::<bool>().map_err(|e| ConfigError::Invalid {
+                setting: "DICI_S3_PATH_STYLE_ACCESS",
+                reason: e.to_string(),
+            })
+        })
+        .transpose()?;
+    Ok(S3Options {
+        endpoint: env::var("DICI_S3_ENDPOINT").ok(),
+        region: env::var("DICI_S3_REGION").ok(),
+        path_style_access,
+    })
 }
 pub type Warehouse = String;
-#[derive(TypedBuilder, Clone)]
+pub type CatalogUri = String;
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum CatalogBackend {
+    #[default]
+    Glue,
+    Rest {
+        uri: CatalogUri,
+    },
+    Sql {
+        uri: CatalogUri,
+    },
+    Memory,
+    Filesystem,
+}
+impl CatalogBackend {
+    pub fn from_kind(kind: &str, uri: Option<CatalogUri>) -> Result<Self, ConfigError> {
+        let missing_uri = || ConfigError::Missing {
+            setting: "Catalog uri",
+            env: "DICI_CATALOG_URI",
+            flag: "catalog-uri",
+            key: "catalog.uri",
+        };
+        match kind.to_lowercase().as_str() {
+            "glue" => Ok(Self::Glue),
+            "rest" => Ok(Self::Rest {
+                uri: uri.ok_or_else(missing_uri)?,
+            }),
+            "sql" => Ok(Self::Sql {
+                uri: uri.ok_or_else(missing_uri)?,
+            }),
+            "memory" => Ok(Self::Memory),
+            "filesystem" => Ok(Self::Filesystem),
+            other => Err(ConfigError::Invalid {
+                setting: "catalog backend",
+                reason: format!(
+                    "'{}' is not one of glue, rest, sql, memory, filesystem",
+                    other
+                ),
+            }),
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct GlueOptions {
+    pub region: Option<String>,
+    pub endpoint: Option<String>,
+    pub profile: Option<String>,
+}
+impl GlueOptions {
+    pub fn merge(self, other: GlueOptions) -> GlueOptions {
+        GlueOptions {
+            region: other.region.or(self.region),
+            endpoint: other.endpoint.or(self.endpoint),
+            profile: other.profile.or(self.profile),
+        }
+    }
+    pub fn props(&self) -> HashMap<String, String> {
+        [
+            (AWS_REGION_NAME, self.region.clone()),
+            (AWS_PROFILE_NAME, self.profile.clone()),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|value| (key.to_string(), value)))
+        .collect()
+    }
+}
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct S3Options {
+    pub endpoint: Option<String>,
+    pub region: Option<String>,
+    pub path_style_access: Option<bool>,
+}
+impl S3Options {
+    pub fn merge(self, other: S3Options) -> S3Options {
+        S3Options {
+            endpoint: other.endpoint.or(self.endpoint),
+            region: other.region.or(self.region),
+            path_style_access: other.path_style_access.or(self.path_style_access),
+        }
+    }
+    pub fn props(&self) -> HashMap<String, String> {
+        [
+            (S3_ENDPOINT, self.endpoint.clone()),
+            (S3_REGION, self.region.clone()),
+            (
+                S3_PATH_STYLE_ACCESS,
+                self.path_style_access.map(|value| value.to_string()),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|value| (key.to_string(), value)))
+        .collect()
+    }
+}
+// Unset values fall back to the environment when the catalog is constructed.
+#[derive(TypedBuilder, Clone, Default)]
 pub struct DiciConfig {
-    #[builder(default = default_warehouse(), setter(into))]
-    pub warehouse: Warehouse,
+    #[builder(default, setter(into, strip_option(fallback = warehouse_opt)))]
+    pub warehouse: Option<Warehouse>,
+    #[builder(default, setter(strip_option(fallback = backend_opt)))]
+    pub backend: Option<CatalogBackend>,
+    #[builder(default)]
+    pub glue: GlueOptions,
+    #[builder(default)]
+    pub s3: S3Options,
+    #[builder(default, setter(strip_option(fallback = table_cache_ttl_opt)))]
+    pub table_cache_ttl: Option<Duration>,
 }
-impl Default for DiciConfig {
-    fn default() -> Self {
-        Self::builder().build()
+impl DiciConfig {
+    pub fn warehouse(&self) -> Result<Warehouse, ConfigError> {
+        self.warehouse
+            .clone()
+            .or_else(warehouse_from_env)
+            .ok_or(ConfigError::Missing {
+                setting: "Warehouse",
+                env: "DICI_WAREHOUSE",
+                flag: "warehouse",
+                key: "warehouse",
+            })
+    }
+    pub fn backend(&self) -> Result<CatalogBackend, ConfigError> {
+        match &self.backend {
+            Some(backend) => Ok(backend.clone()),
+            None => Ok(backend_from_env()?.unwrap_or_default()),
+        }
+    }
+    pub fn glue(&self) -> GlueOptions {
+        glue_options_from_env().merge(self.glue.clone())
+    }
+    pub fn table_cache_ttl(&self) -> Duration {
+        self.table_cache_ttl.unwrap_or(DEFAULT_TABLE_CACHE_TTL)
+    }
+    pub fn s3(&self) -> Result<S3Options, ConfigError> {
+        Ok(s3_options_from_env()?.merge(self.s3.clone()))
     }
 }
@@ -1,27 +1,150 @@
+use crate::api::store::catalog::cache::TableCache;
 use crate::api::store::catalog::catalog_source::CatalogSource;
-use crate::api::store::catalog::config::DiciConfig;
+use crate::api::store::catalog::config::{CatalogBackend, DiciConfig};
+use crate::api::store::catalog::filesystem::FilesystemCatalog;
 use anyhow::Context;
 use anyhow::Result;
+use iceberg::io::FileIO;
+use iceberg::table::Table;
+use iceberg::{Catalog, MemoryCatalog, TableIdent};
 use iceberg_catalog_glue::{GlueCatalog, GlueCatalogConfig};
+use iceberg_catalog_rest::{RestCatalog, RestCatalogConfig};
+use iceberg_catalog_sql::{SqlBindStyle, SqlCatalog, SqlCatalogConfig};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
 use typed_builder::TypedBuilder;
 #[derive(TypedBuilder, Clone)]
 pub struct DiciCatalog {
     #[builder(default)]
     config: DiciConfig,
+    #[builder(default, setter(strip_option))]
+    catalog: Option<Arc<dyn Catalog>>,
+    #[builder(default, setter(skip))]
+    shared: Arc<OnceCell<Arc<dyn Catalog>>>,
+    #[builder(default, setter(skip))]
+    cache: Arc<TableCache>,
 }
 impl Default for DiciCatalog {
     fn default() -> Self {
         Self::builder().build()
     }
 }
+impl DiciCatalog {
+    pub fn config(&self) -> &DiciConfig {
+        &self.config
+    }
+    pub fn cache(&self) -> &TableCache {
+        &self.cache
+    }
+    pub async fn resolve_ident<F>(&self, key: &str, resolve: F) -> Result<TableIdent>
+    where
+        F: Future<Output = Result<TableIdent>>,
+    {
+        let ttl = self.config.table_cache_ttl();
+        if let Some(ident) = self.cache.ident(key, ttl) {
+            return Ok(ident);
+        }
+        let ident = resolve.await?;
+        self.cache.store_ident(key, ident.clone());
+        Ok(ident)
+    }
+    fn file_io(&self, warehouse: &str, props: &HashMap<String, String>) -> Result<FileIO> {
+        FileIO::from_path(warehouse)
+            .context("Failed to resolve file io for warehouse")?
+            .with_props(props)
+            .build()
+            .context("Failed to construct file io")
+    }
+    async fn construct(&self) -> Result<Arc<dyn Catalog>> {
+        let warehouse = self.config.warehouse()?;
+        let props = self.config.s3()?.props();
+        Ok(match self.config.backend()? {
+            CatalogBackend::Glue => {
+                let glue = self.config.glue();
+                let props: HashMap<String, String> =
+                    props.into_iter().chain(glue.props()).collect();
+                let config = match glue.endpoint {
+                    Some(endpoint) => GlueCatalogConfig::builder()
+                        .uri(endpoint)
+                        .warehouse(warehouse)
+                        .props(props)
+                        .build(),
+                    None => GlueCatalogConfig::builder()
+                        .warehouse(warehouse)
+                        .props(props)
+                        .build(),
+                };
+                Arc::new(
+                    GlueCatalog::new(config)
+                        .await
+                        .context("Failed to construct glue catalog")?,
+                )
+            }
+            CatalogBackend::Rest { uri } => Arc::new(RestCatalog::new(
+                RestCatalogConfig::builder()
+                    .uri(uri)
+                    .warehouse(warehouse)
+                    .props(props)
+                    .build(),
+            )),
+            CatalogBackend::Sql { uri } => {
+                let sql_bind_style = if uri.starts_with("postgres") {
+                    SqlBindStyle::DollarNumeric
+                } else {
+                    SqlBindStyle::QMark
+                };
+                let file_io = self.file_io(&warehouse, &props)?;
+                Arc::new(
+                    SqlCatalog::new(
+                        SqlCatalogConfig::builder()
+                            .uri(uri)
+                            .name("dici".to_string())
+                            .warehouse_location(warehouse)
+                            .file_io(file_io)
+                            .sql_bind_style(sql_bind_style)
+                            .build(),
+                    )
+                    .await
+                    .context("Failed to construct sql catalog")?,
+                )
+            }
+            CatalogBackend::Memory => Arc::new(MemoryCatalog::new(
+                self.file_io(&warehouse, &props)?,
+                Some(warehouse),
+            )),
+            CatalogBackend::Filesystem => Arc::new(FilesystemCatalog::new(
+                &warehouse,
+                self.file_io(&warehouse, &props)?,
+            )),
+        })
+    }
+}
 impl CatalogSource for DiciCatalog {
-    async fn catalog(&self) -> Result<GlueCatalog> {
-        GlueCatalog::new(
-            GlueCatalogConfig::builder()
-                .warehouse(self.config.warehouse.clone())
-                .build(),
-        )
-        .await
-        .context("Failed to construct glue catalog")
+    async fn catalog(&self) -> Result<Arc<dyn Catalog>> {
+        match &self.catalog {
+            Some(catalog) => Ok(catalog.clone()),
+            None => self
+                .shared
+                .get_or_try_init(|| self.construct())
+                .await
+                .cloned(),
+        }
+    }
+    // Served from the cache for `table_cache_ttl`, a table committed to since may be that much behind.
+    // Once stale the catalog is asked again, and the table loaded before is kept while its metadata
+    // location is unchanged.
+    async fn load_table(&self, ident: &TableIdent) -> Result<Table> {
+        if let Some(table) = self.cache.table(ident, self.config.table_cache_ttl()) {
+            return Ok(table);
+        }
+        let table = self
+            .catalog()
+            .await
+            .context("Failed to construct catalog")?
+            .load_table(ident)
+            .await
+            .context("Failed to load table")?;
+        Ok(self.cache.store_table(ident, table))
     }
 }
@@ -0,0 +1,226 @@
+use async_trait::async_trait;
+use iceberg::io::FileIO;
+use iceberg::spec::TableMetadata;
+use iceberg::table::Table;
+use iceberg::{
+    Catalog, Error, ErrorKind, Namespace, NamespaceIdent, Result, TableCommit, TableCreation,
+    TableIdent,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+const METADATA_DIR: &str = "metadata";
+const METADATA_SUFFIX: &str = ".metadata.json";
+const VERSION_HINT: &str = "version-hint.text";
+// A read only catalog over a directory laid out as <warehouse>/<namespace>/<table>/metadata/*.metadata.json
+#[derive(Debug)]
+pub struct FilesystemCatalog {
+    root: PathBuf,
+    file_io: FileIO,
+}
+impl FilesystemCatalog {
+    // The root is made absolute, metadata locations are file:// urls which can't hold a relative path.
+    pub fn new(warehouse: &str, file_io: FileIO) -> Self {
+        let root = warehouse.strip_prefix("file://").unwrap_or(warehouse);
+        let root = std::fs::canonicalize(root)
+            .or_else(|_| std::path::absolute(root))
+            .unwrap_or_else(|_| PathBuf::from(root));
+        Self { root, file_io }
+    }
+    fn namespace_path(&self, namespace: &NamespaceIdent) -> PathBuf {
+        namespace
+            .clone()
+            .inner()
+            .iter()
+            .fold(self.root.clone(), |path, part| path.join(part))
+    }
+    fn table_path(&self, table: &TableIdent) -> PathBuf {
+        self.namespace_path(table.namespace()).join(table.name())
+    }
+    async fn is_table(path: &Path) -> bool {
+        fs::metadata(path.join(METADATA_DIR))
+            .await
+            .map(|metadata| metadata.is_dir())
+            .unwrap_or(false)
+    }
+    async fn child_dirs(path: &Path) -> Result<Vec<(String, PathBuf)>> {
+        let mut entries = fs::read_dir(path).await.map_err(|e| {
+            Error::new(
+                ErrorKind::DataInvalid,
+                format!("Failed to read directory {}", path.display()),
+            )
+            .with_source(e)
+        })?;
+        let mut dirs = Vec::new();
+        while let Some(entry) = entries.next_entry().await.map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "Failed to read directory entry").with_source(e)
+        })? {
+            let is_dir = entry.file_type().await.map(|t| t.is_dir()).unwrap_or(false);
+            if is_dir && let Some(name) = entry.file_name().to_str() {
+                dirs.push((name.to_string(), entry.path()));
+            }
+        }
+        dirs.sort();
+        Ok(dirs)
+    }
+    async fn latest_metadata_file(table_path: &Path) -> Result<PathBuf> {
+        let metadata_dir = table_path.join(METADATA_DIR);
+        if let Ok(hint) = fs::read_to_string(metadata_dir.join(VERSION_HINT)).await {
+            let hinted = metadata_dir.join(format!("v{}{}", hint.trim(), METADATA_SUFFIX));
+            if fs::try_exists(&hinted).await.unwrap_or(false) {
+                return Ok(hinted);
+            }
+        }
+        let mut entries = fs::read_dir(&metadata_dir).await.map_err(|e| {
+            Error::new(
+                ErrorKind::DataInvalid,
+                format!("Table metadata not found at {}", metadata_dir.display()),
+            )
+            .with_source(e)
+        })?;
+        let mut latest: Option<(u64, PathBuf)> = None;
+        while let Some(entry) = entries.next_entry().await.map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "Failed to read directory entry").with_source(e)
+        })? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(stem) = name.strip_suffix(METADATA_SUFFIX) else {
+                continue;
+            };
+            let version = stem
+                .trim_start_matches('v')
+                .split('-')
+                .next()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(0);
+            if latest
+                .as_ref()
+                .is_none_or(|(current, _)| version >= *current)
+            {
+                latest = Some((version, entry.path()));
+            }
+        }
+        latest.map(|(_, path)| path).ok_or_else(|| {
+            Error::new(
+                ErrorKind::DataInvalid,
+                format!("No metadata files found in {}", metadata_dir.display()),
+            )
+        })
+    }
+    fn read_only<T>(operation: &str) -> Result<T> {
+        Err(Error::new(
+            ErrorKind::FeatureUnsupported,
+            format!(
+                "The filesystem catalog is read only, {} is not supported",
+                operation
+            ),
+        ))
+    }
+}
+#[async_trait]
+impl Catalog for FilesystemCatalog {
+    async fn list_namespaces(
+        &self,
+        parent: Option<&NamespaceIdent>,
+    ) -> Result<Vec<NamespaceIdent>> {
+        let (path, prefix) = match parent {
+            Some(parent) => (self.namespace_path(parent), parent.clone().inner()),
+            None => (self.root.clone(), vec![]),
+        };
+        let mut namespaces = Vec::new();
+        for (name, dir) in Self::child_dirs(&path).await? {
+            if !Self::is_table(&dir).await {
+                let mut parts = prefix.clone();
+                parts.push(name);
+                namespaces.push(NamespaceIdent::from_vec(parts)?);
+            }
+        }
+        Ok(namespaces)
+    }
+    async fn create_namespace(
+        &self,
+        _namespace: &NamespaceIdent,
+        _properties: HashMap<String, String>,
+    ) -> Result<Namespace> {
+        Self::read_only("create_namespace")
+    }
+    async fn get_namespace(&self, namespace: &NamespaceIdent) -> Result<Namespace> {
+        if self.namespace_exists(namespace).await? {
+            Ok(Namespace::new(namespace.clone()))
+        } else {
+            Err(Error::new(
+                ErrorKind::DataInvalid,
+                format!("Namespace {:?} does not exist", namespace),
+            ))
+        }
+    }
+    async fn namespace_exists(&self, namespace: &NamespaceIdent) -> Result<bool> {
+        Ok(fs::metadata(self.namespace_path(namespace))
+            .await
+            .map(|metadata| metadata.is_dir())
+            .unwrap_or(false))
+    }
+    async fn update_namespace(
+        &self,
+        _namespace: &NamespaceIdent,
+        _properties: HashMap<String, String>,
+    ) -> Result<()> {
+        Self::read_only("update_namespace")
+    }
+    async fn drop_namespace(&self, _namespace: &NamespaceIdent) -> Result<()> {
+        Self::read_only("drop_namespace")
+    }
+    async fn list_tables(&self, namespace: &NamespaceIdent) -> Result<Vec<TableIdent>> {
+        let mut tables = Vec::new();
+        for (name, dir) in Self::child_dirs(&self.namespace_path(namespace)).await? {
+            if Self::is_table(&dir).await {
+                tables.push(TableIdent::new(namespace.clone(), name));
+            }
+        }
+        Ok(tables)
+    }
+    async fn create_table(
+        &self,
+        _namespace: &NamespaceIdent,
+        _creation: TableCreation,
+    ) -> Result<Table> {
+        Self::read_only("create_table")
+    }
+    async fn load_table(&self, table: &TableIdent) -> Result<Table> {
+        let metadata_path = Self::latest_metadata_file(&self.table_path(table)).await?;
+        let metadata_location = format!("file://{}", metadata_path.display());
+        let bytes = self.file_io.new_input(&metadata_location)?.read().await?;
+        let metadata: TableMetadata = serde_json::from_slice(&bytes).map_err(|e| {
+            Error::new(
+                ErrorKind::DataInvalid,
+                format!("Failed to parse table metadata at {}", metadata_location),
+            )
+            .with_source(e)
+        })?;
+        Table::builder()
+            .file_io(self.file_io.clone())
+            .metadata_location(metadata_location)
+            .metadata(metadata)
+            .identifier(table.clone())
+            .readonly(true)
+            .build()
+    }
+    async fn drop_table(&self, _table: &TableIdent) -> Result<()> {
+        Self::read_only("drop_table")
+    }
+    async fn table_exists(&self, table: &TableIdent) -> Result<bool> {
+        Ok(Self::is_table(&self.table_path(table)).await)
+    }
+    async fn rename_table(&self, _src: &TableIdent, _dest: &TableIdent) -> Result<()> {
+        Self::read_only("rename_table")
+    }
+    async fn register_table(
+        &self,
+        _table: &TableIdent,
+        _metadata_location: String,
+    ) -> Result<Table> {
+        Self::read_only("register_table")
+    }
+    async fn update_table(&self, _commit: TableCommit) -> Result<Table> {
+        Self::read_only("update_table")
+    }
+}
@@ -1,3 +1,7 @@
+pub mod browse;
+pub mod cache;
 pub mod catalog_source;
 pub mod config;
 pub mod dici;
+pub mod filesystem;
+pub mod provider;
@@ -0,0 +1,152 @@
+use crate::api::http::management::api::ManagementApi;
+use crate::api::store::asset::core::CoreAsset;
+use crate::api::store::asset::dici::{CoreArgs, DiciAsset, IcebergArgs};
+use crate::api::store::asset::iceberg::IcebergAsset;
+use crate::api::store::asset::traits::table_source::TableSource;
+use crate::api::store::asset::version::TableVersion;
+use crate::api::store::catalog::dici::DiciCatalog;
+use async_trait::async_trait;
+use datafusion::catalog::{CatalogProvider, SchemaProvider, TableProvider};
+use datafusion::error::{DataFusionError, Result};
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+// Schema of the default catalog that resolves fourByFours, e.g. core."abcd-1234".
+pub const CORE_SCHEMA: &str = "core";
+// Catalog whose schemas are iceberg locations, e.g. iceberg."_0123abcd"."dbo_vendors".
+pub const ICEBERG_CATALOG: &str = "iceberg";
+async fn table_provider(asset: DiciAsset) -> Result<Option<Arc<dyn TableProvider>>> {
+    let provider = asset
+        .table_provider()
+        .await
+        .map_err(|e| DataFusionError::External(e.into()))?;
+    Ok(Some(Arc::new(provider)))
+}
+// Nothing is known up front, tables only exist once a query names them. Every table is read at the
+// given version.
+#[derive(Clone)]
+pub struct CoreSchemaProvider {
+    management_client: Arc<dyn ManagementApi>,
+    dici_catalog: DiciCatalog,
+    version: TableVersion,
+}
+impl CoreSchemaProvider {
+    pub fn new(
+        management_client: Arc<dyn ManagementApi>,
+        dici_catalog: DiciCatalog,
+        version: TableVersion,
+    ) -> Self {
+        Self {
+            management_client,
+            dici_catalog,
+            version,
+        }
+    }
+}
+impl Debug for CoreSchemaProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CoreSchemaProvider").finish_non_exhaustive()
+    }
+}
+#[async_trait]
+impl SchemaProvider for CoreSchemaProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn table_names(&self) -> Vec<String> {
+        vec![]
+    }
+    async fn table(&self, name: &str) -> Result<Option<Arc<dyn TableProvider>>> {
+        let fxf = name
+            .parse()
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        let asset: DiciAsset = CoreArgs::builder()
+            .asset(CoreAsset::builder().fxf(fxf).build())
+            .management_client(self.management_client.clone())
+            .dici_catalog(self.dici_catalog.clone())
+            .version(self.version.clone())
+            .build()
+            .into();
+        table_provider(asset).await
+    }
+    fn table_exist(&self, _name: &str) -> bool {
+        false
+    }
+}
+#[derive(Clone)]
+pub struct IcebergCatalogProvider {
+    dici_catalog: DiciCatalog,
+    version: TableVersion,
+}
+impl IcebergCatalogProvider {
+    pub fn new(dici_catalog: DiciCatalog, version: TableVersion) -> Self {
+        Self {
+            dici_catalog,
+            version,
+        }
+    }
+}
+impl Debug for IcebergCatalogProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IcebergCatalogProvider")
+            .finish_non_exhaustive()
+    }
+}
+impl CatalogProvider for IcebergCatalogProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn schema_names(&self) -> Vec<String> {
+        vec![]
+    }
+    fn schema(&self, name: &str) -> Option<Arc<dyn SchemaProvider>> {
+        Some(Arc::new(IcebergLocationSchemaProvider {
+            dici_catalog: self.dici_catalog.clone(),
+            location: name.to_string(),
+            version: self.version.clone(),
+        }))
+    }
+}
+#[derive(Clone)]
+pub struct IcebergLocationSchemaProvider {
+    dici_catalog: DiciCatalog,
+    location: String,
+    version: TableVersion,
+}
+impl Debug for IcebergLocationSchemaProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IcebergLocationSchemaProvider")
+            .field("location", &self.location)
+            .finish_non_exhaustive()
+    }
+}
+#[async_trait]
+impl SchemaProvider for IcebergLocationSchemaProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn table_names(&self) -> Vec<String> {
+        vec![]
+    }
+    async fn table(&self, name: &str) -> Result<Option<Arc<dyn TableProvider>>> {
+        let location = self
+            .location
+            .parse()
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        let asset: DiciAsset = IcebergArgs::builder()
+            .asset(
+                IcebergAsset::builder()
+                    .location(location)
+                    .schema_table(name)
+                    .build(),
+            )
+            .dici_catalog(self.dici_catalog.clone())
+            .version(self.version.clone())
+            .build()
+            .into();
+        table_provider(asset).await
+    }
+    fn table_exist(&self, _name: &str) -> bool {
+        false
+    }
+}
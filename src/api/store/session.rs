@@ -0,0 +1,123 @@
+use crate::api::http::management::api::ManagementApi;
+use crate::api::store::asset::changes::TableChanges;
+use crate::api::store::asset::traits::table_source::TableSource;
+use crate::api::store::asset::version::TableVersion;
+use crate::api::store::catalog::dici::DiciCatalog;
+use crate::api::store::catalog::provider::{
+    CORE_SCHEMA, CoreSchemaProvider, ICEBERG_CATALOG, IcebergCatalogProvider,
+};
+use anyhow::{Context, Result};
+use datafusion::dataframe::DataFrame;
+use datafusion::prelude::{SQLOptions, SessionContext};
+use datafusion::sql::TableReference;
+use futures::future::try_join_all;
+use std::sync::Arc;
+pub fn read_only_sql_options() -> SQLOptions {
+    SQLOptions::new()
+        .with_allow_ddl(false)
+        .with_allow_dml(false)
+        .with_allow_statements(false)
+}
+// A single session that many assets can be registered into under their own aliases, so they can be joined.
+#[derive(Default, Clone)]
+pub struct DiciSession {
+    ctx: SessionContext,
+}
+impl DiciSession {
+    pub fn new(ctx: SessionContext) -> Self {
+        Self { ctx }
+    }
+    pub fn context(&self) -> &SessionContext {
+        &self.ctx
+    }
+    pub fn into_context(self) -> SessionContext {
+        self.ctx
+    }
+    // Makes core."<fxf>" and iceberg."<icebergLocation>"."<schemaTable>" resolvable without registering them
+    // first, each read at the given version.
+    pub fn register_providers(
+        &self,
+        management_client: &Arc<dyn ManagementApi>,
+        dici_catalog: &DiciCatalog,
+        version: &TableVersion,
+    ) -> Result<()> {
+        let default_catalog = self
+            .ctx
+            .state()
+            .config()
+            .options()
+            .catalog
+            .default_catalog
+            .clone();
+        self.ctx
+            .catalog(&default_catalog)
+            .context("Default catalog is missing from the session")?
+            .register_schema(
+                CORE_SCHEMA,
+                Arc::new(CoreSchemaProvider::new(
+                    management_client.clone(),
+                    dici_catalog.clone(),
+                    version.clone(),
+                )),
+            )
+            .context("Failed to register the core schema")?;
+        self.ctx.register_catalog(
+            ICEBERG_CATALOG,
+            Arc::new(IcebergCatalogProvider::new(
+                dici_catalog.clone(),
+                version.clone(),
+            )),
+        );
+        Ok(())
+    }
+    pub async fn register<T: TableSource>(
+        &self,
+        alias: impl Into<TableReference>,
+        asset: &T,
+    ) -> Result<()> {
+        let alias = alias.into();
+        let table_provider = Arc::new(
+            asset
+                .table_provider()
+                .await
+                .with_context(|| format!("Failed to load table for {}", alias))?,
+        );
+        self.ctx
+            .register_table(alias, table_provider)
+            .context("Failed to register table")?;
+        Ok(())
+    }
+    pub fn register_changes(
+        &self,
+        alias: impl Into<TableReference>,
+        changes: &TableChanges,
+    ) -> Result<()> {
+        self.ctx
+            .register_table(alias, Arc::new(changes.table_provider()?))
+            .context("Failed to register changes table")?;
+        Ok(())
+    }
+    pub async fn register_all<'a, T, A, I>(&self, assets: I) -> Result<()>
+    where
+        T: TableSource + 'a,
+        A: Into<TableReference>,
+        I: IntoIterator<Item = (A, &'a T)>,
+    {
+        try_join_all(
+            assets
+                .into_iter()
+                .map(|(alias, asset)| self.register(alias, asset)),
+        )
+        .await?;
+        Ok(())
+    }
+    pub async fn sql(&self, sql: &str) -> Result<DataFrame> {
+        self.ctx.sql(sql).await.context("Failed to execute query")
+    }
+    pub async fn sql_with_options(&self, sql: &str, options: SQLOptions) -> Result<DataFrame> {
+        self.ctx
+            .sql_with_options(sql, options)
+            .await
+            .context("Failed to execute query")
+    }
+}
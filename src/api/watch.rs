@@ -0,0 +1,230 @@
+use crate::api::fs::write_atomic;
+use crate::api::http::management::api::ManagementApi;
+use crate::api::http::management::model::inventory::Inventory;
+use crate::api::retry::Retry;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::time::Duration;
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum InventoryEventKind {
+    Created,
+    Updated,
+}
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryEvent {
+    pub kind: InventoryEventKind,
+    pub observed_at: DateTime<Utc>,
+    pub inventory: Inventory,
+}
+fn inventory_key(inventory: &Inventory) -> String {
+    format!(
+        "{}/{}/{}",
+        inventory.id.domain.domain,
+        inventory.id.iceberg_location.iceberg_location,
+        inventory.id.schema_table.schema_table
+    )
+}
+// Where the watch left off: the latest updatedAt seen and the inventories updated at exactly that time,
+// so a poll that includes its `since` doesn't emit them twice.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchCheckpoint {
+    pub since: DateTime<Utc>,
+    #[serde(default)]
+    pub seen: BTreeSet<String>,
+}
+impl WatchCheckpoint {
+    pub fn new(since: DateTime<Utc>) -> Self {
+        Self {
+            since,
+            seen: BTreeSet::new(),
+        }
+    }
+    // None when the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let checkpoint = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read checkpoint {}", path.display()))?;
+        serde_json::from_str(&checkpoint)
+            .map(Some)
+            .with_context(|| format!("Failed to parse checkpoint {}", path.display()))
+    }
+    pub fn save(&self, path: &Path) -> Result<()> {
+        write_atomic(path, &serde_json::to_vec_pretty(self)?, false)
+            .with_context(|| format!("Failed to write checkpoint {}", path.display()))
+    }
+    // Inventories created or updated since the checkpoint, oldest first. The checkpoint only moves
+    // forward once the caller has handled the events, see advance.
+    pub async fn poll(&self, management_client: &dyn ManagementApi) -> Result<Vec<InventoryEvent>> {
+        let observed_at = Utc::now();
+        let mut inventories: Vec<Inventory> = management_client
+            .fetch_inventories_updated_since(self.since)
+            .await?
+            .into_iter()
+            .filter(|inventory| match inventory.updated_at {
+                updated_at if updated_at > self.since => true,
+                updated_at if updated_at == self.since => {
+                    !self.seen.contains(&inventory_key(inventory))
+                }
+                _ => false,
+            })
+            .collect();
+        inventories.sort_by_key(|inventory| inventory.updated_at);
+        Ok(inventories
+            .into_iter()
+            .map(|inventory| InventoryEvent {
+                kind: match inventory.created_at > self.since {
+                    true => InventoryEventKind::Created,
+                    false => InventoryEventKind::Updated,
+                },
+                observed_at,
+                inventory,
+            })
+            .collect())
+    }
+    pub fn advance(&mut self, events: &[InventoryEvent]) {
+        for event in events {
+            let updated_at = event.inventory.updated_at;
+            if updated_at > self.since {
+                self.since = updated_at;
+                self.seen.clear();
+            }
+            if updated_at == self.since {
+                self.seen.insert(inventory_key(&event.inventory));
+            }
+        }
+    }
+}
+const WEBHOOK_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const WEBHOOK_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const WEBHOOK_BACKOFF: Duration = Duration::from_millis(500);
+// Posts events to a webhook as JSON, one request per event. A webhook that hangs times out like one
+// that fails, so the watch doesn't stall. Only refused connections and 5xx are retried, a 4xx won't
+// change on its own and a timed out post may have been delivered.
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    http_client: reqwest::Client,
+    url: Url,
+    retry: Retry,
+}
+impl Webhook {
+    pub fn new(url: Url, retries: u32) -> Result<Self> {
+        let http_client = reqwest::Client::builder()
+            .connect_timeout(WEBHOOK_CONNECT_TIMEOUT)
+            .timeout(WEBHOOK_REQUEST_TIMEOUT)
+            .build()
+            .context("Failed to construct webhook http client")?;
+        Ok(Self {
+            http_client,
+            url,
+            retry: Retry::new(retries, WEBHOOK_BACKOFF),
+        })
+    }
+    pub async fn post(&self, event: &InventoryEvent) -> Result<()> {
+        let (http_client, url) = (&self.http_client, &self.url);
+        let (attempts, result) = self
+            .retry
+            .run(
+                move || async move {
+                    http_client
+                        .post(url.clone())
+                        .json(event)
+                        .send()
+                        .await?
+                        .error_for_status()
+                },
+                |result| matches!(result, Err(e) if e.is_connect() || e.status().is_some_and(|status| status.is_server_error())),
+            )
+            .await;
+        result.map(|_| ()).with_context(|| {
+            format!(
+                "Failed to post event to webhook {} after {} attempts",
+                self.url, attempts
+            )
+        })
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::http::management::memory::InMemoryManagement;
+    use serde_json::json;
+    fn at(datetime: &str) -> DateTime<Utc> {
+        datetime.parse().unwrap()
+    }
+    fn inventory(schema_table: &str, created_at: &str, updated_at: &str) -> Inventory {
+        serde_json::from_value(json!({
+            "id": {
+                "domain": {"domain": "data.example.gov"},
+                "icebergLocation": {"icebergLocation": "_0123456789abcdef0123456789abcdef"},
+                "schemaTable": {"schemaTable": schema_table}
+            },
+            "fourByFour": {"fourByFour": "abcd-1234"},
+            "createdAt": created_at,
+            "updatedAt": updated_at
+        }))
+        .unwrap()
+    }
+    fn event(schema_table: &str, updated_at: &str) -> InventoryEvent {
+        InventoryEvent {
+            kind: InventoryEventKind::Updated,
+            observed_at: Utc::now(),
+            inventory: inventory(schema_table, "2024-01-01T00:00:00Z", updated_at),
+        }
+    }
+    #[test]
+    fn advance_keeps_the_inventories_at_the_latest_time() {
+        let mut checkpoint = WatchCheckpoint::new(at("2024-01-01T00:00:00Z"));
+        checkpoint.advance(&[
+            event("a.first", "2024-01-02T00:00:00Z"),
+            event("a.second", "2024-01-03T00:00:00Z"),
+            event("a.third", "2024-01-03T00:00:00Z"),
+        ]);
+        assert_eq!(checkpoint.since, at("2024-01-03T00:00:00Z"));
+        assert_eq!(checkpoint.seen.len(), 2);
+        assert!(checkpoint.seen.iter().all(|key| !key.ends_with("a.first")));
+        let before = checkpoint.clone();
+        checkpoint.advance(&[event("a.old", "2024-01-02T00:00:00Z")]);
+        checkpoint.advance(&[]);
+        assert_eq!(checkpoint, before);
+    }
+    #[tokio::test]
+    async fn poll_skips_inventories_already_seen() {
+        let management = InMemoryManagement::new(
+            vec![
+                inventory("a.old", "2024-01-01T00:00:00Z", "2024-01-01T00:00:00Z"),
+                inventory("a.updated", "2024-01-01T00:00:00Z", "2024-01-03T00:00:00Z"),
+                inventory("a.created", "2024-01-02T12:00:00Z", "2024-01-02T12:00:00Z"),
+            ],
+            vec![],
+        );
+        let mut checkpoint = WatchCheckpoint::new(at("2024-01-02T00:00:00Z"));
+        let events = checkpoint.poll(&management).await.unwrap();
+        let polled: Vec<_> = events
+            .iter()
+            .map(|event| {
+                (
+                    event.inventory.id.schema_table.schema_table.as_str(),
+                    event.kind,
+                )
+            })
+            .collect();
+        assert_eq!(
+            polled,
+            [
+                ("a.created", InventoryEventKind::Created),
+                ("a.updated", InventoryEventKind::Updated)
+            ]
+        );
+        checkpoint.advance(&events);
+        assert!(checkpoint.poll(&management).await.unwrap().is_empty());
+    }
+}
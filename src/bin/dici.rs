@@ -1,7 +1,10 @@
-use anyhow::Result;
 use clap::Parser;
-use diceberg::cli::DiciCli;
+use diceberg::cli::{DiciCli, report_error};
+use std::process::ExitCode;
 #[tokio::main]
-async fn main() -> Result<()> {
-    DiciCli::parse().run().await
+async fn main() -> ExitCode {
+    match DiciCli::parse().run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => report_error(&error),
+    }
 }
@@ -1,3 +1,4 @@
+use crate::cli::GlobalArgs;
 use crate::term::duplex::Duplex;
 use crate::term::llm_chat_sink::{CancelCtl, ChatEvent, MistralDuplexSink};
 use crate::term::llm_chat_ui_source::MistralDuplexSourceUi;
@@ -6,14 +7,19 @@ use mistralrs::{IsqType, ModelDType, PagedAttentionMetaBuilder, RequestBuilder,
 #[cfg(feature = "mcp")]
 use mistralrs::{McpClientConfig, McpServerConfig, McpServerSource};
 use std::sync::Arc;
-pub async fn handle_ai() -> Result<()> {
+#[cfg_attr(not(feature = "mcp"), allow(unused_variables))]
+pub async fn handle_ai(global: &GlobalArgs) -> Result<()> {
     #[cfg(feature = "mcp")]
     let mcp = McpClientConfig {
         servers: vec![McpServerConfig {
             name: "The data and insights cloud integration (DICI) model context protocol (MCP) server.".into(),
             source: McpServerSource::Process {
                 command: "dici".into(),
-                args: vec!["serve".into(), "mcp".into()],
+                args: global
+                    .to_args()
+                    .into_iter()
+                    .chain(["serve".into(), "mcp".into()])
+                    .collect(),
                 work_dir: None,
                 env: None,
             },
@@ -34,7 +40,8 @@ pub async fn handle_ai() -> Result<()> {
     let model = model
         .with_max_num_seqs(3)
         .with_prefix_cache_n(Some(5))
-        .build().await?;
+        .build()
+        .await?;
     let (source, sink) = Duplex::unbounded::<RequestBuilder, ChatEvent, CancelCtl>();
     let _worker = MistralDuplexSink::new(sink, Arc::new(model)).spawn();
     let mut ui = MistralDuplexSourceUi::new(source);
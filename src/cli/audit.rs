@@ -0,0 +1,19 @@
+use crate::api::context::DiciContext;
+use anyhow::{Context, Result, bail};
+use clap::Args;
+#[derive(Args, Clone)]
+pub struct AuditArgs {
+    // Sync the tables of stale inventories through dici management.
+    #[arg(long)]
+    sync: bool,
+}
+// Writes the report to stdout and fails when problems remain, so scripts can act on the exit code.
+pub async fn handle_audit(args: AuditArgs, ctx: &DiciContext) -> Result<()> {
+    let report = ctx.audit(args.sync).await?;
+    serde_json::to_writer_pretty(std::io::stdout(), &report)
+        .context("failed to serialize report")?;
+    match report.problems() {
+        0 => Ok(()),
+        problems => bail!("Audit found {} problems", problems),
+    }
+}
@@ -0,0 +1,39 @@
+use crate::api::http::management::memory::InMemoryManagement;
+use crate::api::http::management::mock::{read_fixtures, serve};
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
+use tokio::net::TcpListener;
+#[derive(Subcommand, Clone)]
+pub enum DevCommand {
+    // Serve every dici management endpoint from fixture files, point DICI_MANAGEMENT_ADDRESS at it.
+    MockManagement(MockManagementArgs),
+}
+#[derive(Args, Clone)]
+pub struct MockManagementArgs {
+    // A snapshot from dici management export, or a directory of snapshot.json, inventories.json,
+    // registrations.json and version.json.
+    #[arg(long)]
+    pub fixtures: PathBuf,
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+    // 0 picks a free port, the address is printed on stderr either way.
+    #[arg(long, default_value_t = 8080)]
+    pub port: u16,
+}
+pub async fn handle_dev(dev_command: DevCommand) -> Result<()> {
+    match dev_command {
+        DevCommand::MockManagement(args) => handle_mock_management(args).await,
+    }
+}
+async fn handle_mock_management(args: MockManagementArgs) -> Result<()> {
+    let management = InMemoryManagement::from(read_fixtures(&args.fixtures)?);
+    let listener = TcpListener::bind((args.host.as_str(), args.port))
+        .await
+        .with_context(|| format!("Failed to bind {}:{}", args.host, args.port))?;
+    eprintln!(
+        "Mock dici management listening on http://{}",
+        listener.local_addr()?
+    );
+    serve(listener, management).await
+}
@@ -0,0 +1,33 @@
+use crate::api::context::DiciContext;
+use crate::api::store::catalog::browse::CatalogBrowser;
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+#[derive(Subcommand, Clone)]
+pub enum InfoCatalogCommand {
+    Namespaces,
+    Tables(NamespaceArgs),
+}
+#[derive(Args, Clone)]
+pub struct NamespaceArgs {
+    pub namespace: String,
+}
+pub async fn handle_info_catalog(
+    info_catalog_command: InfoCatalogCommand,
+    ctx: &DiciContext,
+) -> Result<()> {
+    match info_catalog_command {
+        InfoCatalogCommand::Namespaces => {
+            let namespaces = ctx.dici_catalog.namespaces().await?;
+            serde_json::to_writer_pretty(std::io::stdout(), &namespaces)
+                .context("failed to serialize namespaces")
+        }
+        InfoCatalogCommand::Tables(NamespaceArgs { namespace }) => {
+            let tables = ctx
+                .dici_catalog
+                .tables_with_inventories(&namespace, ctx.management_client.as_ref())
+                .await?;
+            serde_json::to_writer_pretty(std::io::stdout(), &tables)
+                .context("failed to serialize tables")
+        }
+    }
+}
@@ -1,24 +1,53 @@
-use crate::api::http::management::client::ManagementClient;
+use crate::api::context::DiciContext;
+use crate::api::identifier::{Domain, FourByFour, IcebergLocation};
+use crate::api::since::parse_since;
 use anyhow::Context;
+use chrono::{DateTime, Utc};
 use clap::{Args, Subcommand};
 #[derive(Subcommand, Clone)]
 pub enum InventoryLookupCommand {
     All,
     Fxf(FxfArgs),
     Iceberg(IcebergArgs),
+    Domain(DomainArgs),
+    Since(SinceArgs),
+    Id(IdArgs),
+    Table(TableArgs),
 }
 #[derive(Args, Clone)]
 pub struct FxfArgs {
-    fxf: String,
+    fxf: FourByFour,
 }
 #[derive(Args, Clone)]
 pub struct IcebergArgs {
-    location: String,
+    location: IcebergLocation,
+}
+#[derive(Args, Clone)]
+pub struct DomainArgs {
+    domain: Domain,
+}
+#[derive(Args, Clone)]
+pub struct SinceArgs {
+    // An RFC 3339 datetime or a duration back from now, like 2h or 1d.
+    #[arg(value_parser = parse_since)]
+    since: DateTime<Utc>,
+}
+#[derive(Args, Clone)]
+pub struct IdArgs {
+    domain: Domain,
+    location: IcebergLocation,
+    schema_table: String,
+}
+#[derive(Args, Clone)]
+pub struct TableArgs {
+    location: IcebergLocation,
+    schema_table: String,
 }
 pub async fn handle_lookup_inventory(
     inventory_lookup_command: InventoryLookupCommand,
+    ctx: &DiciContext,
 ) -> anyhow::Result<()> {
-    let dici_management_client = ManagementClient::default();
+    let dici_management_client = &ctx.management_client;
     match inventory_lookup_command {
         InventoryLookupCommand::All => {
             let inventories = dici_management_client.fetch_inventories().await?;
@@ -26,13 +55,48 @@ pub async fn handle_lookup_inventory(
                 .context("failed to serialize inventories")
         }
         InventoryLookupCommand::Fxf(FxfArgs { fxf }) => {
-            let inventories = dici_management_client.fetch_inventory_by_fxf(fxf).await?;
+            let inventories = dici_management_client.fetch_inventory_by_fxf(&fxf).await?;
             serde_json::to_writer_pretty(std::io::stdout(), &inventories)
                 .context("failed to serialize inventory")
         }
         InventoryLookupCommand::Iceberg(IcebergArgs { location }) => {
             let inventories = dici_management_client
-                .fetch_inventories_by_iceberg_location(location)
+                .fetch_inventories_by_iceberg_location(&location)
+                .await?;
+            serde_json::to_writer_pretty(std::io::stdout(), &inventories)
+                .context("failed to serialize inventories")
+        }
+        InventoryLookupCommand::Domain(DomainArgs { domain }) => {
+            let inventories = dici_management_client
+                .fetch_inventories_by_domain(&domain)
+                .await?;
+            serde_json::to_writer_pretty(std::io::stdout(), &inventories)
+                .context("failed to serialize inventories")
+        }
+        InventoryLookupCommand::Since(SinceArgs { since }) => {
+            let inventories = dici_management_client
+                .fetch_inventories_updated_since(since)
+                .await?;
+            serde_json::to_writer_pretty(std::io::stdout(), &inventories)
+                .context("failed to serialize inventories")
+        }
+        InventoryLookupCommand::Id(IdArgs {
+            domain,
+            location,
+            schema_table,
+        }) => {
+            let inventory = dici_management_client
+                .fetch_inventory_by_id(&domain, &location, &schema_table)
+                .await?;
+            serde_json::to_writer_pretty(std::io::stdout(), &inventory)
+                .context("failed to serialize inventory")
+        }
+        InventoryLookupCommand::Table(TableArgs {
+            location,
+            schema_table,
+        }) => {
+            let inventories = dici_management_client
+                .fetch_inventories_by_iceberg_location_and_table(&location, &schema_table)
                 .await?;
             serde_json::to_writer_pretty(std::io::stdout(), &inventories)
                 .context("failed to serialize inventories")
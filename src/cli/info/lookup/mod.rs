@@ -1,5 +1,6 @@
 pub mod inventory;
 pub mod registration;
+use crate::api::context::DiciContext;
 use crate::cli::info::lookup::inventory::{InventoryLookupCommand, handle_lookup_inventory};
 use crate::cli::info::lookup::registration::{
     RegistrationLookupCommand, handle_lookup_registration,
@@ -13,9 +14,12 @@ pub enum InfoLookupCommand {
     #[clap(subcommand)]
     Inventory(InventoryLookupCommand),
 }
-pub async fn handle_lookup(info_lookup_command: InfoLookupCommand) -> Result<()> {
+pub async fn handle_lookup(
+    info_lookup_command: InfoLookupCommand,
+    ctx: &DiciContext,
+) -> Result<()> {
     match info_lookup_command {
-        InfoLookupCommand::Registration(args) => handle_lookup_registration(args).await,
-        InfoLookupCommand::Inventory(args) => handle_lookup_inventory(args).await,
+        InfoLookupCommand::Registration(args) => handle_lookup_registration(args, ctx).await,
+        InfoLookupCommand::Inventory(args) => handle_lookup_inventory(args, ctx).await,
     }
 }
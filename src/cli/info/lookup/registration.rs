@@ -1,4 +1,5 @@
-use crate::api::http::management::client::ManagementClient;
+use crate::api::context::DiciContext;
+use crate::api::identifier::{IcebergLocation, RegistrationPath};
 use anyhow::Context;
 use clap::{Args, Subcommand};
 use std::collections::HashMap;
@@ -7,25 +8,31 @@ pub enum RegistrationLookupCommand {
     All,
     Path(PathArgs),
     Filtered(MetadataArgs),
+    Iceberg(IcebergArgs),
 }
 #[derive(Args, Clone)]
 pub struct MetadataArgs {
-    path: String,
+    path: RegistrationPath,
     pairs: Vec<String>,
 }
 #[derive(Args, Clone)]
 pub struct PathArgs {
-    path: String,
+    path: RegistrationPath,
+}
+#[derive(Args, Clone)]
+pub struct IcebergArgs {
+    location: IcebergLocation,
 }
 pub async fn handle_lookup_registration(
     registration_lookup_command: RegistrationLookupCommand,
+    ctx: &DiciContext,
 ) -> anyhow::Result<()> {
-    let dici_management_client = ManagementClient::default();
+    let dici_management_client = &ctx.management_client;
     let registrations = match registration_lookup_command {
         RegistrationLookupCommand::All => dici_management_client.fetch_registrations().await?,
         RegistrationLookupCommand::Path(args) => {
             dici_management_client
-                .fetch_registrations_by_path(args.path)
+                .fetch_registrations_by_path(&args.path)
                 .await?
         }
         RegistrationLookupCommand::Filtered(MetadataArgs { path, pairs }) => {
@@ -34,9 +41,17 @@ pub async fn handle_lookup_registration(
                 .map(|chunk| (chunk[0].clone(), chunk[1].clone()))
                 .collect();
             dici_management_client
-                .fetch_registrations_by_path_and_metadata(path, metadata)
+                .fetch_registrations_by_path_and_metadata(&path, &metadata)
                 .await?
         }
+        // A location has at most one registration.
+        RegistrationLookupCommand::Iceberg(IcebergArgs { location }) => {
+            let registration = dici_management_client
+                .fetch_registration_by_iceberg_location(&location)
+                .await?;
+            return serde_json::to_writer_pretty(std::io::stdout(), &registration)
+                .context("failed to serialize registration");
+        }
     };
     serde_json::to_writer_pretty(std::io::stdout(), &registrations)
         .context("failed to serialize registrations")
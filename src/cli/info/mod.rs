@@ -1,19 +1,47 @@
+use crate::api::config::error::ConfigError;
+use crate::api::context::DiciContext;
+use crate::cli::info::catalog::{InfoCatalogCommand, handle_info_catalog};
 use crate::cli::info::lookup::{InfoLookupCommand, handle_lookup};
 use crate::cli::info::table::{InfoTableCommand, handle_info_table};
-use anyhow::Result;
+use crate::cli::print_warning;
+use anyhow::{Context, Result};
 use clap::Subcommand;
+pub mod catalog;
 pub mod lookup;
 pub mod table;
 #[derive(Subcommand, Clone)]
 pub enum InfoCommand {
     #[clap(subcommand)]
-    Table(InfoTableCommand),
+    Table(Box<InfoTableCommand>),
     #[clap(subcommand)]
     Lookup(InfoLookupCommand),
+    #[clap(subcommand)]
+    Catalog(InfoCatalogCommand),
+    Version,
 }
-pub async fn handle_info(info_command: InfoCommand) -> Result<()> {
+pub async fn handle_info(
+    info_command: InfoCommand,
+    ctx: impl FnOnce() -> Result<DiciContext, ConfigError>,
+) -> Result<()> {
     match info_command {
-        InfoCommand::Table(args) => handle_info_table(args).await,
-        InfoCommand::Lookup(args) => handle_lookup(args).await,
+        InfoCommand::Table(args) => handle_info_table(*args, &ctx()?).await,
+        InfoCommand::Lookup(args) => handle_lookup(args, &ctx()?).await,
+        InfoCommand::Catalog(args) => handle_info_catalog(args, &ctx()?).await,
+        // The cli version needs no config, management is left out with a warning when it can't load.
+        InfoCommand::Version => {
+            let management = match ctx() {
+                Ok(ctx) => Some(ctx.management_client.fetch_version().await?),
+                Err(e) => {
+                    print_warning(&format!("management version skipped: {}", e));
+                    None
+                }
+            };
+            let version = serde_json::json!({
+                "dici": env!("CARGO_PKG_VERSION"),
+                "management": management,
+            });
+            serde_json::to_writer_pretty(std::io::stdout(), &version)
+                .context("failed to serialize version")
+        }
     }
 }
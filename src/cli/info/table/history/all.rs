@@ -1,12 +1,10 @@
+use crate::api::context::DiciContext;
 use crate::api::store::asset::dici::DiciAsset;
 use crate::api::store::asset::traits::table_source::TableSource;
 use crate::cli::info::table::AssetArgs;
 use anyhow::{Context, Result};
-pub async fn handle_info_table_history_all(asset_args: AssetArgs) -> Result<()> {
-    let asset: DiciAsset = match asset_args {
-        AssetArgs::Core(core) => core.into(),
-        AssetArgs::Iceberg(iceberg) => iceberg.into(),
-    };
+pub async fn handle_info_table_history_all(asset_args: AssetArgs, ctx: &DiciContext) -> Result<()> {
+    let asset: DiciAsset = asset_args.into_asset(ctx);
     let table = asset.table().await?;
     serde_json::to_writer_pretty(std::io::stdout(), table.metadata().history())
         .context("failed to serialize table history")
@@ -1,21 +1,23 @@
+use crate::api::context::DiciContext;
 use crate::cli::info::table::AssetArgs;
 use crate::cli::info::table::history::all::handle_info_table_history_all;
 use crate::cli::info::table::history::snapshot::handle_info_table_snapshot;
 use anyhow::Result;
 use clap::Subcommand;
-use snapshot::SnapshotCommand;
+use snapshot::SnapshotArgs;
 mod all;
 pub mod snapshot;
 #[derive(Subcommand, Clone)]
 pub enum HistoryCommand {
-    #[clap(subcommand)]
     All(AssetArgs),
-    #[clap(subcommand)]
-    Snapshot(SnapshotCommand),
+    Snapshot(SnapshotArgs),
 }
-pub async fn handle_info_table_history(history_command: HistoryCommand) -> Result<()> {
+pub async fn handle_info_table_history(
+    history_command: HistoryCommand,
+    ctx: &DiciContext,
+) -> Result<()> {
     match history_command {
-        HistoryCommand::All(args) => handle_info_table_history_all(args).await,
-        HistoryCommand::Snapshot(args) => handle_info_table_snapshot(args).await,
+        HistoryCommand::All(args) => handle_info_table_history_all(args, ctx).await,
+        HistoryCommand::Snapshot(args) => handle_info_table_snapshot(args, ctx).await,
     }
 }
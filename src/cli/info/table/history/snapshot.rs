@@ -1,43 +1,20 @@
-use crate::api::store::asset::dici::DiciAsset;
+use crate::api::context::DiciContext;
 use crate::api::store::asset::traits::table_source::TableSource;
-use crate::cli::info::table::{CoreAssetArgs, IcebergAssetArgs};
+use crate::cli::info::table::AssetArgs;
 use anyhow::{Context, Result};
-use clap::{Args, Subcommand};
-#[derive(Subcommand, Clone)]
-pub enum SnapshotCommand {
-    Core(SnapshotCoreArgs),
-    Iceberg(SnapshotIcebergArgs),
-}
+use clap::Args;
 #[derive(Args, Clone)]
 pub struct SnapshotArgs {
-    pub snapshot: i64,
-}
-#[derive(Args, Clone)]
-pub struct SnapshotCoreArgs {
-    #[clap(flatten)]
-    pub core: CoreAssetArgs,
     #[clap(flatten)]
-    pub snapshot: SnapshotArgs,
-}
-#[derive(Args, Clone)]
-pub struct SnapshotIcebergArgs {
-    #[clap(flatten)]
-    pub iceberg: IcebergAssetArgs,
-    #[clap(flatten)]
-    pub snapshot: SnapshotArgs,
+    pub asset: AssetArgs,
+    pub snapshot: i64,
 }
-pub async fn handle_info_table_snapshot(snapshot_command: SnapshotCommand) -> Result<()> {
-    let (asset, snapshot): (DiciAsset, i64) = match snapshot_command {
-        SnapshotCommand::Core(SnapshotCoreArgs {
-            core,
-            snapshot: SnapshotArgs { snapshot },
-        }) => (core.into(), snapshot),
-        SnapshotCommand::Iceberg(SnapshotIcebergArgs {
-            iceberg,
-            snapshot: SnapshotArgs { snapshot },
-        }) => (iceberg.into(), snapshot),
-    };
-    let table = asset.table().await?;
+pub async fn handle_info_table_snapshot(
+    snapshot_args: SnapshotArgs,
+    ctx: &DiciContext,
+) -> Result<()> {
+    let SnapshotArgs { asset, snapshot } = snapshot_args;
+    let table = asset.into_asset(ctx).table().await?;
     let snapshot = table
         .metadata()
         .snapshot_by_id(snapshot)
@@ -1,3 +1,4 @@
+use crate::api::context::DiciContext;
 use crate::api::store::asset::dici::DiciAsset;
 use crate::cli::info::table::history::{HistoryCommand, handle_info_table_history};
 use crate::cli::info::table::partition::handle_info_table_partition;
@@ -11,44 +12,31 @@ pub mod schema;
 pub mod stats;
 #[derive(Subcommand, Clone)]
 pub enum InfoTableCommand {
-    #[clap(subcommand)]
     Schema(AssetArgs),
-    #[clap(subcommand)]
     Partition(AssetArgs),
     #[clap(subcommand)]
     History(HistoryCommand),
     #[clap(subcommand)]
     Stats(StatsCommand),
 }
-#[derive(Subcommand, Clone)]
-pub enum AssetArgs {
-    Core(CoreAssetArgs),
-    Iceberg(IcebergAssetArgs),
-}
-#[derive(Args, Clone)]
-pub struct CoreAssetArgs {
-    pub fxf: String,
-}
-impl Into<DiciAsset> for CoreAssetArgs {
-    fn into(self) -> DiciAsset {
-        DiciAsset::core(self.fxf)
-    }
-}
 #[derive(Args, Clone)]
-pub struct IcebergAssetArgs {
-    pub location: String,
-    pub schema_table: String,
+pub struct AssetArgs {
+    // core:<fxf>, iceberg:<icebergLocation>/<schemaTable>, inv:<domain>/<icebergLocation>/<schemaTable> or reg:<path>#<schemaTable>
+    pub asset: DiciAsset,
 }
-impl Into<DiciAsset> for IcebergAssetArgs {
-    fn into(self) -> DiciAsset {
-        DiciAsset::iceberg(self.location, self.schema_table)
+impl AssetArgs {
+    pub fn into_asset(self, ctx: &DiciContext) -> DiciAsset {
+        ctx.asset(self.asset)
     }
 }
-pub async fn handle_info_table(info_table_command: InfoTableCommand) -> Result<()> {
+pub async fn handle_info_table(
+    info_table_command: InfoTableCommand,
+    ctx: &DiciContext,
+) -> Result<()> {
     match info_table_command {
-        InfoTableCommand::Schema(args) => handle_info_table_schema(args).await,
-        InfoTableCommand::Partition(args) => handle_info_table_partition(args).await,
-        InfoTableCommand::History(args) => handle_info_table_history(args).await,
-        InfoTableCommand::Stats(args) => handle_info_table_stats(args).await,
+        InfoTableCommand::Schema(args) => handle_info_table_schema(args, ctx).await,
+        InfoTableCommand::Partition(args) => handle_info_table_partition(args, ctx).await,
+        InfoTableCommand::History(args) => handle_info_table_history(args, ctx).await,
+        InfoTableCommand::Stats(args) => handle_info_table_stats(args, ctx).await,
     }
 }
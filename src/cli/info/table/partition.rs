@@ -1,12 +1,10 @@
+use crate::api::context::DiciContext;
 use crate::api::store::asset::dici::DiciAsset;
 use crate::api::store::asset::traits::table_source::TableSource;
 use crate::cli::info::table::AssetArgs;
 use anyhow::{Context, Result};
-pub async fn handle_info_table_partition(asset_args: AssetArgs) -> Result<()> {
-    let asset: DiciAsset = match asset_args {
-        AssetArgs::Core(args) => args.into(),
-        AssetArgs::Iceberg(args) => args.into(),
-    };
+pub async fn handle_info_table_partition(asset_args: AssetArgs, ctx: &DiciContext) -> Result<()> {
+    let asset: DiciAsset = asset_args.into_asset(ctx);
     let table = asset.table().await?;
     let metadata = table.metadata();
     serde_json::to_writer_pretty(std::io::stdout(), metadata.default_partition_spec())
@@ -0,0 +1,39 @@
+use crate::api::config::error::ConfigError;
+use crate::api::context::DiciContext;
+use crate::api::http::management::snapshot::{SnapshotDiff, SnapshotManagement};
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
+#[derive(Subcommand, Clone)]
+pub enum ManagementCommand {
+    // Write every inventory and registration as a snapshot, for --management-snapshot.
+    Export,
+    // Show the inventories and registrations added, removed or changed between two snapshots.
+    Diff(ManagementDiffArgs),
+}
+#[derive(Args, Clone)]
+pub struct ManagementDiffArgs {
+    before: PathBuf,
+    after: PathBuf,
+}
+pub async fn handle_management(
+    management_command: ManagementCommand,
+    ctx: impl FnOnce() -> Result<DiciContext, ConfigError>,
+) -> Result<()> {
+    match management_command {
+        ManagementCommand::Export => {
+            let ctx = ctx()?;
+            let snapshot = SnapshotManagement::export(ctx.management_client.as_ref()).await?;
+            serde_json::to_writer_pretty(std::io::stdout(), &snapshot)
+                .context("failed to serialize snapshot")
+        }
+        ManagementCommand::Diff(ManagementDiffArgs { before, after }) => {
+            let diff = SnapshotDiff::between(
+                &SnapshotManagement::read(&before)?,
+                &SnapshotManagement::read(&after)?,
+            )?;
+            serde_json::to_writer_pretty(std::io::stdout(), &diff)
+                .context("failed to serialize diff")
+        }
+    }
+}
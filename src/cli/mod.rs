@@ -1,48 +1,210 @@
+use crate::api::config::error::ConfigError;
+use crate::api::config::file::Profile;
+use crate::api::config::loader::ConfigLoader;
+use crate::api::context::DiciContext;
+use crate::api::error::ErrorReport;
+use crate::api::http::management::cache::CacheMode;
+use crate::api::http::management::config::ManagementOptions;
+use crate::api::store::catalog::config::{CatalogBackend, GlueOptions};
 #[cfg(feature = "ai")]
 use crate::cli::ai::handle_ai;
+use crate::cli::audit::{AuditArgs, handle_audit};
+#[cfg(feature = "dev")]
+use crate::cli::dev::{DevCommand, handle_dev};
 use crate::cli::info::{InfoCommand, handle_info};
+use crate::cli::management::{ManagementCommand, handle_management};
+use crate::cli::resolve::{ResolveArgs, handle_resolve};
 #[cfg(feature = "mcp")]
 use crate::cli::serve::{ServeCommand, handle_serve};
 use crate::cli::sql::{SqlCommand, handle_sql};
+use crate::cli::sync::{SyncCommand, handle_sync};
 use crate::cli::util::{UtilCommand, handle_util};
+use crate::cli::watch::{WatchCommand, handle_watch};
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+use std::process::ExitCode;
 #[cfg(feature = "ai")]
 pub mod ai;
+pub mod audit;
+#[cfg(feature = "dev")]
+pub mod dev;
 pub mod info;
+pub mod management;
+pub mod resolve;
 pub mod serve;
 pub mod sql;
+pub mod sync;
 pub mod util;
+pub mod watch;
 #[derive(Parser)]
 #[command(version)]
 pub struct DiciCli {
+    #[clap(flatten)]
+    pub global: GlobalArgs,
     #[command(subcommand)]
     pub command: Commands,
 }
+#[derive(Args, Clone, Default)]
+pub struct GlobalArgs {
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+    #[arg(long, global = true)]
+    pub warehouse: Option<String>,
+    #[arg(long, global = true)]
+    pub management_address: Option<String>,
+    // Answer from a file written by dici management export instead of dici management.
+    #[arg(long, global = true)]
+    pub management_snapshot: Option<PathBuf>,
+    #[arg(long, global = true)]
+    pub catalog: Option<String>,
+    #[arg(long, global = true)]
+    pub catalog_uri: Option<String>,
+    #[arg(long, global = true)]
+    pub aws_profile: Option<String>,
+    // Skip the dici management response cache, for this run only.
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+}
+impl GlobalArgs {
+    fn catalog_backend(&self) -> Result<Option<CatalogBackend>, ConfigError> {
+        self.catalog
+            .as_deref()
+            .map(|kind| CatalogBackend::from_kind(kind, self.catalog_uri.clone()))
+            .transpose()
+    }
+    // `cache` is the management cache used unless the config or --no-cache says otherwise.
+    pub fn loader(&self, cache: CacheMode) -> Result<ConfigLoader, ConfigError> {
+        Ok(ConfigLoader::builder()
+            .path_opt(self.config.clone())
+            .profile_opt(self.profile.clone())
+            .defaults(Profile {
+                management: ManagementOptions {
+                    cache: Some(cache),
+                    ..ManagementOptions::default()
+                },
+                ..Profile::default()
+            })
+            .overrides(Profile {
+                warehouse: self.warehouse.clone(),
+                management_address: self.management_address.clone(),
+                management: ManagementOptions {
+                    cache: self.no_cache.then_some(CacheMode::Off),
+                    ..ManagementOptions::default()
+                },
+                management_snapshot: self.management_snapshot.clone(),
+                catalog: self.catalog_backend()?,
+                glue: GlueOptions {
+                    profile: self.aws_profile.clone(),
+                    ..GlueOptions::default()
+                },
+                ..Profile::default()
+            })
+            .build())
+    }
+    pub fn context(&self, cache: CacheMode) -> Result<DiciContext, ConfigError> {
+        Ok(self.loader(cache)?.load()?.into())
+    }
+    // Forwards the global flags to child dici processes, like the mcp server spawned by the ai chat.
+    pub fn to_args(&self) -> Vec<String> {
+        [
+            (
+                "--config",
+                self.config.as_ref().map(|p| p.display().to_string()),
+            ),
+            ("--profile", self.profile.clone()),
+            ("--warehouse", self.warehouse.clone()),
+            ("--management-address", self.management_address.clone()),
+            (
+                "--management-snapshot",
+                self.management_snapshot
+                    .as_ref()
+                    .map(|p| p.display().to_string()),
+            ),
+            ("--catalog", self.catalog.clone()),
+            ("--catalog-uri", self.catalog_uri.clone()),
+            ("--aws-profile", self.aws_profile.clone()),
+        ]
+        .into_iter()
+        .filter_map(|(flag, value)| value.map(|value| [flag.to_string(), value]))
+        .flatten()
+        .chain(self.no_cache.then(|| "--no-cache".to_string()))
+        .collect()
+    }
+}
 #[derive(Subcommand, Clone)]
 pub enum Commands {
     #[clap(subcommand)]
     Info(InfoCommand),
+    Sql(Box<SqlCommand>),
+    Resolve(ResolveArgs),
+    Audit(AuditArgs),
+    #[clap(subcommand)]
+    Sync(SyncCommand),
+    #[clap(subcommand)]
+    Watch(WatchCommand),
     #[clap(subcommand)]
-    Sql(SqlCommand),
+    Management(ManagementCommand),
     #[clap(subcommand)]
     Util(UtilCommand),
     #[cfg(feature = "mcp")]
     #[clap(subcommand)]
     Serve(ServeCommand),
+    #[cfg(feature = "dev")]
+    #[clap(subcommand)]
+    Dev(DevCommand),
     #[cfg(feature = "ai")]
     Ai,
 }
+impl Commands {
+    // The mcp server lives as long as its session and caches in memory, cli runs share a disk cache.
+    fn cache_mode(&self) -> CacheMode {
+        #[cfg(feature = "mcp")]
+        if matches!(self, Commands::Serve(_)) {
+            return CacheMode::Memory;
+        }
+        CacheMode::Disk
+    }
+}
 impl DiciCli {
     pub async fn run(self) -> Result<()> {
+        // Loaded per command, so a broken config doesn't stop the ones that never touch it.
+        let cache = self.command.cache_mode();
+        let ctx = || self.global.context(cache);
         match self.command {
-            Commands::Info(args) => handle_info(args).await,
-            Commands::Sql(args) => handle_sql(args).await,
-            Commands::Util(args) => handle_util(args).await,
+            Commands::Info(args) => handle_info(args, ctx).await,
+            Commands::Sql(args) => handle_sql(*args, &ctx()?).await,
+            Commands::Resolve(args) => handle_resolve(args, &ctx()?).await,
+            Commands::Audit(args) => handle_audit(args, &ctx()?).await,
+            Commands::Sync(args) => handle_sync(args, &ctx()?).await,
+            Commands::Watch(args) => handle_watch(args, &ctx()?).await,
+            Commands::Management(args) => handle_management(args, ctx).await,
+            Commands::Util(args) => handle_util(args, &ctx()?).await,
             #[cfg(feature = "mcp")]
-            Commands::Serve(args) => handle_serve(args).await,
+            Commands::Serve(args) => handle_serve(args, ctx()?).await,
+            #[cfg(feature = "dev")]
+            Commands::Dev(args) => handle_dev(args).await,
             #[cfg(feature = "ai")]
-            Commands::Ai => handle_ai().await,
+            Commands::Ai => handle_ai(&self.global).await,
         }
     }
 }
+// Errors go to stderr as one line of JSON, with an exit code telling scripts what kind of failure it was.
+pub fn report_error(error: &anyhow::Error) -> ExitCode {
+    ExitCode::from(print_error(error).kind.exit_code())
+}
+// Warnings share stderr with errors as their own kind of JSON line, e.g. {"warning":"..."}.
+pub fn print_warning(warning: &str) {
+    eprintln!("{}", serde_json::json!({ "warning": warning }));
+}
+// The same line for errors a long running command recovers from.
+pub fn print_error(error: &anyhow::Error) -> ErrorReport {
+    let report = ErrorReport::from(error);
+    match serde_json::to_string(&report) {
+        Ok(json) => eprintln!("{}", json),
+        Err(_) => eprintln!("{:#}", error),
+    }
+    report
+}
@@ -0,0 +1,13 @@
+use crate::api::context::DiciContext;
+use crate::api::resolve::Identifier;
+use anyhow::{Context, Result};
+use clap::Args;
+#[derive(Args, Clone)]
+pub struct ResolveArgs {
+    // A fourByFour, icebergLocation, <icebergLocation>/<schemaTable>, domain, inventory id, registration path or asset.
+    identifier: Identifier,
+}
+pub async fn handle_resolve(args: ResolveArgs, ctx: &DiciContext) -> Result<()> {
+    let graph = ctx.resolve(args.identifier).await?;
+    serde_json::to_writer_pretty(std::io::stdout(), &graph).context("failed to serialize graph")
+}
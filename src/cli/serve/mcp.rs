@@ -1,3 +1,5 @@
+use crate::api::context::DiciContext;
+use crate::cli::serve::McpArgs;
 use crate::mcp::handler::DiciServerHandler;
 use rust_mcp_sdk::schema::{
     Implementation, InitializeResult, LATEST_PROTOCOL_VERSION, ServerCapabilities,
@@ -8,12 +10,12 @@ use rust_mcp_sdk::{
     error::SdkResult,
     mcp_server::{ServerRuntime, server_runtime},
 };
-pub async fn handle_serve_mcp() -> anyhow::Result<()> {
-    run_mcp()
+pub async fn handle_serve_mcp(args: McpArgs, ctx: DiciContext) -> anyhow::Result<()> {
+    run_mcp(args, ctx)
         .await
         .map_err(|e| anyhow::Error::msg(e.to_string()))
 }
-async fn run_mcp() -> SdkResult<()> {
+async fn run_mcp(args: McpArgs, ctx: DiciContext) -> SdkResult<()> {
     let server_details = InitializeResult {
         server_info: Implementation {
             name: "The data and insights cloud integration (DICI) model context protocol (MCP) server.".to_string(),
@@ -29,7 +31,10 @@ async fn run_mcp() -> SdkResult<()> {
         protocol_version: LATEST_PROTOCOL_VERSION.to_string(),
     };
     let transport = StdioTransport::new(TransportOptions::default())?;
-    let handler = DiciServerHandler::default();
+    let handler = DiciServerHandler::builder()
+        .state(ctx.into())
+        .allow_writes(args.allow_writes)
+        .build();
     let server: ServerRuntime = server_runtime::create_server(server_details, transport, handler);
     if let Err(start_error) = server.start().await {
         eprintln!(
@@ -1,16 +1,24 @@
+use crate::api::context::DiciContext;
 #[cfg(feature = "mcp")]
 use crate::cli::serve::mcp::handle_serve_mcp;
-use clap::Subcommand;
+use clap::{Args, Subcommand};
 #[cfg(feature = "mcp")]
 pub mod mcp;
 #[derive(Subcommand, Clone)]
 pub enum ServeCommand {
     #[cfg(feature = "mcp")]
-    MCP,
+    MCP(McpArgs),
 }
-pub async fn handle_serve(serve_command: ServeCommand) -> anyhow::Result<()> {
+#[derive(Args, Clone)]
+pub struct McpArgs {
+    // Also expose the tools that change dici management, like the sync tools.
+    #[arg(long)]
+    pub allow_writes: bool,
+}
+#[cfg_attr(not(feature = "mcp"), allow(unused_variables))]
+pub async fn handle_serve(serve_command: ServeCommand, ctx: DiciContext) -> anyhow::Result<()> {
     match serve_command {
         #[cfg(feature = "mcp")]
-        ServeCommand::MCP => handle_serve_mcp().await,
+        ServeCommand::MCP(args) => handle_serve_mcp(args, ctx).await,
     }
 }
@@ -1,18 +1,41 @@
+use crate::api::context::DiciContext;
 use crate::api::store::asset::dici::DiciAsset;
-use crate::api::store::asset::traits::sqlable::SqlAble;
-use crate::cli::info::table::{CoreAssetArgs, IcebergAssetArgs};
+use crate::api::store::asset::traits::changes_source::ChangesSource;
+use crate::api::store::asset::traits::sqlable::TableReferenceSource;
+use crate::api::store::asset::version::TableVersion;
+use crate::api::store::session::{DiciSession, read_only_sql_options};
+use crate::cli::info::table::AssetArgs;
+use crate::cli::print_warning;
 use anyhow::{Context, Result};
 use arrow::array::RecordBatch;
 use arrow_ipc::writer::StreamWriter;
 use arrow_json::ArrayWriter;
+use chrono::{DateTime, Utc};
 use clap::{Args, Subcommand, ValueEnum};
-use datafusion::prelude::{DataFrame, SQLOptions};
+use datafusion::prelude::DataFrame;
 use std::io;
 use std::io::Write;
+// `dici sql <asset> <query>` queries a single asset under its own table name, the subcommands cover the rest.
+#[derive(Args, Clone)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct SqlCommand {
+    #[command(subcommand)]
+    pub command: Option<SqlSubcommand>,
+    // core:<fxf>, iceberg:<icebergLocation>/<schemaTable>, inv:<domain>/<icebergLocation>/<schemaTable> or reg:<path>#<schemaTable>
+    #[arg(required = true)]
+    pub asset: Option<DiciAsset>,
+    #[arg(required = true)]
+    pub query: Option<String>,
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: SqlOutputFormat,
+    #[clap(flatten)]
+    pub version: TableVersionArgs,
+}
 #[derive(Subcommand, Clone)]
-pub enum SqlCommand {
-    Core(SqlCoreArgs),
-    Iceberg(SqlIcebergArgs),
+pub enum SqlSubcommand {
+    Query(SqlQueryArgs),
+    Multi(SqlMultiArgs),
+    Changes(Box<SqlChangesArgs>),
 }
 #[derive(Args, Clone)]
 pub struct SqlArgs {
@@ -20,22 +43,59 @@ pub struct SqlArgs {
     #[arg(short, long, value_enum, default_value_t)]
     pub format: SqlOutputFormat,
 }
+// Every table named in the query is read at the given version.
 #[derive(Args, Clone)]
-pub struct SqlCoreArgs {
-    #[clap(flatten)]
-    pub core: CoreAssetArgs,
+pub struct SqlQueryArgs {
     #[clap(flatten)]
     pub sql: SqlArgs,
+    #[clap(flatten)]
+    pub version: TableVersionArgs,
 }
 #[derive(Args, Clone)]
-pub struct SqlIcebergArgs {
-    #[clap(flatten)]
-    pub iceberg: IcebergAssetArgs,
+pub struct TableVersionArgs {
+    #[arg(long, conflicts_with_all = ["as_of", "reference"])]
+    pub snapshot: Option<i64>,
+    #[arg(long, conflicts_with = "reference")]
+    pub as_of: Option<DateTime<Utc>>,
+    #[arg(long = "ref")]
+    pub reference: Option<String>,
+}
+impl TableVersionArgs {
+    pub fn into_version(self) -> Result<TableVersion> {
+        TableVersion::from_options(self.snapshot, self.as_of, self.reference)
+    }
+}
+#[derive(Args, Clone)]
+pub struct SqlMultiArgs {
+    #[arg(long = "asset", required = true)]
+    pub assets: Vec<String>,
     #[clap(flatten)]
     pub sql: SqlArgs,
+    #[clap(flatten)]
+    pub version: TableVersionArgs,
 }
-#[derive(Debug, Clone, ValueEnum)]
+// Rows of data files added or removed, rows removed by merge-on-read deletes are not reported.
+#[derive(Args, Clone)]
+pub struct SqlChangesArgs {
+    #[clap(flatten)]
+    pub asset: AssetArgs,
+    #[arg(long)]
+    pub from: i64,
+    // Defaults to the current snapshot.
+    #[arg(long)]
+    pub to: Option<i64>,
+    // The changes are queryable as the table `changes`.
+    #[arg(default_value = "select * from changes")]
+    pub query: String,
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: SqlOutputFormat,
+    // Print what each snapshot changed instead of the changed rows.
+    #[arg(long)]
+    pub summary: bool,
+}
+#[derive(Debug, Clone, Default, ValueEnum)]
 pub enum SqlOutputFormat {
+    #[default]
     JSON,
     IPC,
 }
@@ -61,26 +121,76 @@ impl SqlOutputFormat {
         }
     }
 }
-impl Default for SqlOutputFormat {
-    fn default() -> Self {
-        Self::JSON
+async fn handle_sql_multi(sql_multi_args: SqlMultiArgs, ctx: &DiciContext) -> Result<()> {
+    let SqlMultiArgs {
+        assets,
+        sql: SqlArgs { query, format },
+        version,
+    } = sql_multi_args;
+    let version = version.into_version()?;
+    let assets = ctx.parse_aliased_assets(&assets, &version)?;
+    let session = ctx.session()?;
+    session
+        .register_all(assets.iter().map(|(alias, asset)| (alias.as_str(), asset)))
+        .await?;
+    run_sql(&session, query, format).await
+}
+async fn handle_sql_changes(sql_changes_args: SqlChangesArgs, ctx: &DiciContext) -> Result<()> {
+    let SqlChangesArgs {
+        asset,
+        from,
+        to,
+        query,
+        format,
+        summary,
+    } = sql_changes_args;
+    let changes = asset.into_asset(ctx).changes(from, to).await?;
+    let delete_files = changes.delete_files();
+    if delete_files > 0 && !summary {
+        print_warning(&format!(
+            "{} row level delete files are not reported as deleted rows, see --summary",
+            delete_files
+        ));
+    }
+    if summary {
+        return serde_json::to_writer_pretty(io::stdout(), &changes.snapshots)
+            .context("Failed to serialize snapshot changes");
     }
+    let session = ctx.session()?;
+    session.register_changes("changes", &changes)?;
+    run_sql(&session, query, format).await
 }
-pub async fn handle_sql(sql_command: SqlCommand) -> Result<()> {
-    let (asset, query, format): (DiciAsset, String, SqlOutputFormat) = match sql_command {
-        SqlCommand::Core(SqlCoreArgs {
-            core,
-            sql: SqlArgs { query, format },
-        }) => (core.into(), query, format),
-        SqlCommand::Iceberg(SqlIcebergArgs {
-            iceberg,
-            sql: SqlArgs { query, format },
-        }) => (iceberg.into(), query, format),
-    };
-    let options = SQLOptions::new()
-        .with_allow_ddl(false)
-        .with_allow_dml(false)
-        .with_allow_statements(false);
-    let df = asset.sql_with_options(query.as_str(), options).await?;
+async fn run_sql(session: &DiciSession, query: String, format: SqlOutputFormat) -> Result<()> {
+    let df = session
+        .sql_with_options(query.as_str(), read_only_sql_options())
+        .await?;
     format.to_writer(io::stdout(), df).await
 }
+pub async fn handle_sql(sql_command: SqlCommand, ctx: &DiciContext) -> Result<()> {
+    let SqlCommand {
+        command,
+        asset,
+        query,
+        format,
+        version,
+    } = sql_command;
+    match command {
+        Some(SqlSubcommand::Query(SqlQueryArgs {
+            sql: SqlArgs { query, format },
+            version,
+        })) => run_sql(&ctx.session_at(&version.into_version()?)?, query, format).await,
+        Some(SqlSubcommand::Multi(args)) => handle_sql_multi(args, ctx).await,
+        Some(SqlSubcommand::Changes(args)) => handle_sql_changes(*args, ctx).await,
+        None => {
+            let asset = ctx
+                .asset(asset.context("An asset is required")?)
+                .with_version(version.into_version()?);
+            let query = query.context("A query is required")?;
+            let session = ctx.session()?;
+            session
+                .register(asset.table_reference().await?, &asset)
+                .await?;
+            run_sql(&session, query, format).await
+        }
+    }
+}
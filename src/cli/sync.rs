@@ -0,0 +1,132 @@
+use crate::api::audit::AuditReport;
+use crate::api::bulk_sync::{BulkSync, SyncProgress, SyncTarget};
+use crate::api::context::DiciContext;
+use crate::api::identifier::{Domain, IcebergLocation};
+use anyhow::{Context, Result, bail};
+use clap::{ArgGroup, Args, Subcommand};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+#[derive(Subcommand, Clone)]
+pub enum SyncCommand {
+    // Sync the inventories of a table in every domain it is mapped to.
+    Table(SyncTableArgs),
+    // Sync the inventory of a table in one domain.
+    Domain(SyncDomainArgs),
+    // Sync every table of an icebergLocation.
+    Location(SyncLocationArgs),
+    // Sync many tables concurrently, from a list, a domain or an audit report.
+    Bulk(SyncBulkArgs),
+}
+#[derive(Args, Clone)]
+pub struct SyncTableArgs {
+    location: IcebergLocation,
+    schema_table: String,
+}
+#[derive(Args, Clone)]
+pub struct SyncDomainArgs {
+    domain: Domain,
+    location: IcebergLocation,
+    schema_table: String,
+}
+#[derive(Args, Clone)]
+pub struct SyncLocationArgs {
+    location: IcebergLocation,
+}
+#[derive(Args, Clone)]
+#[command(group(ArgGroup::new("source").required(true).args(["file", "domain", "audit"])))]
+pub struct SyncBulkArgs {
+    // One <icebergLocation>/<schemaTable> or <domain>/<icebergLocation>/<schemaTable> per line, - reads stdin.
+    #[arg(long)]
+    file: Option<PathBuf>,
+    // Every inventory of the domain.
+    #[arg(long)]
+    domain: Option<Domain>,
+    // The report written by dici audit, its stale inventories are synced, - reads stdin.
+    #[arg(long)]
+    audit: Option<PathBuf>,
+    #[arg(long, default_value_t = 8)]
+    concurrency: usize,
+    #[arg(long, default_value_t = 3)]
+    retries: u32,
+}
+fn read_input(path: &Path) -> Result<String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .context("failed to read stdin")?;
+        return Ok(input);
+    }
+    std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+}
+fn print_progress(progress: &SyncProgress) {
+    match &progress.error {
+        None => eprintln!(
+            "[{}/{}] synced {}",
+            progress.completed, progress.total, progress.target
+        ),
+        Some(error) => eprintln!(
+            "[{}/{}] failed {} after {} attempts: {}",
+            progress.completed, progress.total, progress.target, progress.attempts, error
+        ),
+    }
+}
+// Progress goes to stderr and the report to stdout, failures make the command fail after the report is written.
+async fn handle_sync_bulk(args: SyncBulkArgs, ctx: &DiciContext) -> Result<()> {
+    let targets = match (args.file, args.domain, args.audit) {
+        (Some(file), _, _) => SyncTarget::parse_list(&read_input(&file)?)?,
+        (_, Some(domain), _) => SyncTarget::from_domain(ctx.management_client.as_ref(), &domain).await?,
+        (_, _, Some(audit)) => {
+            let report: AuditReport = serde_json::from_str(&read_input(&audit)?)
+                .context("failed to parse audit report")?;
+            SyncTarget::from_audit(&report)
+        }
+        (None, None, None) => bail!("One of --file, --domain or --audit is required"),
+    };
+    let report = BulkSync::builder()
+        .concurrency(args.concurrency)
+        .retries(args.retries)
+        .build()
+        .run(ctx.management_client.as_ref(), targets, print_progress)
+        .await;
+    serde_json::to_writer_pretty(std::io::stdout(), &report)
+        .context("failed to serialize sync report")?;
+    match report.failures.len() {
+        0 => Ok(()),
+        failures => bail!("{} targets failed to sync", failures),
+    }
+}
+pub async fn handle_sync(sync_command: SyncCommand, ctx: &DiciContext) -> Result<()> {
+    let dici_management_client = &ctx.management_client;
+    match sync_command {
+        SyncCommand::Table(SyncTableArgs {
+            location,
+            schema_table,
+        }) => {
+            let inventories = dici_management_client
+                .sync_table(&location, &schema_table)
+                .await?;
+            serde_json::to_writer_pretty(std::io::stdout(), &inventories)
+                .context("failed to serialize inventories")
+        }
+        SyncCommand::Domain(SyncDomainArgs {
+            domain,
+            location,
+            schema_table,
+        }) => {
+            let inventory = dici_management_client
+                .sync_table_domain(&domain, &location, &schema_table)
+                .await?;
+            serde_json::to_writer_pretty(std::io::stdout(), &inventory)
+                .context("failed to serialize inventory")
+        }
+        SyncCommand::Location(SyncLocationArgs { location }) => {
+            let sync = dici_management_client
+                .sync_iceberg_location(&location)
+                .await?;
+            serde_json::to_writer_pretty(std::io::stdout(), &sync)
+                .context("failed to serialize sync result")
+        }
+        SyncCommand::Bulk(args) => handle_sync_bulk(args, ctx).await,
+    }
+}
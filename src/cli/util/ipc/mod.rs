@@ -1,5 +1,6 @@
 pub mod print;
 pub mod query;
+use crate::api::context::DiciContext;
 use crate::cli::util::ipc::print::handle_util_ipc_print;
 use crate::cli::util::ipc::query::{IpcQueryArgs, handle_util_ipc_query};
 use anyhow::Result;
@@ -9,9 +10,9 @@ pub enum IpcCommand {
     Print,
     Query(IpcQueryArgs),
 }
-pub async fn handle_util_ipc(ipc_command: IpcCommand) -> Result<()> {
+pub async fn handle_util_ipc(ipc_command: IpcCommand, ctx: &DiciContext) -> Result<()> {
     match ipc_command {
         IpcCommand::Print => handle_util_ipc_print().await,
-        IpcCommand::Query(args) => handle_util_ipc_query(args).await,
+        IpcCommand::Query(args) => handle_util_ipc_query(args, ctx).await,
     }
 }
@@ -1,4 +1,5 @@
 pub mod ipc;
+use crate::api::context::DiciContext;
 use crate::cli::util::ipc::{IpcCommand, handle_util_ipc};
 use anyhow::Result;
 use clap::Subcommand;
@@ -7,8 +8,8 @@ pub enum UtilCommand {
     #[clap(subcommand)]
     IPC(IpcCommand),
 }
-pub async fn handle_util(util_command: UtilCommand) -> Result<()> {
+pub async fn handle_util(util_command: UtilCommand, ctx: &DiciContext) -> Result<()> {
     match util_command {
-        UtilCommand::IPC(args) => handle_util_ipc(args).await,
+        UtilCommand::IPC(args) => handle_util_ipc(args, ctx).await,
     }
 }
@@ -0,0 +1,103 @@
+use crate::api::context::DiciContext;
+use crate::api::since::{parse_duration, parse_since};
+use crate::api::watch::{WatchCheckpoint, Webhook};
+use crate::cli::print_error;
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use clap::{Args, Subcommand};
+use reqwest::Url;
+use std::io::Write;
+use std::path::PathBuf;
+const MIN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+#[derive(Subcommand, Clone)]
+pub enum WatchCommand {
+    // Poll for created and updated inventories and print them as NDJSON.
+    Inventories(WatchInventoriesArgs),
+}
+#[derive(Args, Clone)]
+pub struct WatchInventoriesArgs {
+    // Time between polls, e.g. 30s or 5m, at least a second.
+    #[arg(long, default_value = "1m", value_parser = parse_duration)]
+    interval: chrono::Duration,
+    // Where the watch keeps its position, so a restart picks up where it left off.
+    #[arg(long)]
+    checkpoint: Option<PathBuf>,
+    // Where to start when there is no checkpoint yet, a datetime or a duration back from now. Defaults to now.
+    #[arg(long, value_parser = parse_since)]
+    since: Option<DateTime<Utc>>,
+    // Also POST each event as JSON to this url.
+    #[arg(long)]
+    webhook: Option<Url>,
+    #[arg(long, default_value_t = 3)]
+    webhook_retries: u32,
+    // Poll once and exit.
+    #[arg(long)]
+    once: bool,
+}
+// Events are delivered before the checkpoint is saved, so after a failure they are sent again.
+async fn watch_step(
+    checkpoint: &mut WatchCheckpoint,
+    webhook: Option<&Webhook>,
+    path: Option<&PathBuf>,
+    ctx: &DiciContext,
+) -> Result<()> {
+    let events = checkpoint.poll(ctx.management_client.as_ref()).await?;
+    let mut stdout = std::io::stdout().lock();
+    for event in &events {
+        serde_json::to_writer(&mut stdout, event).context("failed to serialize event")?;
+        writeln!(stdout).context("failed to write event")?;
+    }
+    stdout.flush().context("failed to flush events")?;
+    drop(stdout);
+    if let Some(webhook) = webhook {
+        for event in &events {
+            webhook.post(event).await?;
+        }
+    }
+    checkpoint.advance(&events);
+    if let Some(path) = path {
+        checkpoint.save(path)?;
+    }
+    Ok(())
+}
+// A failed poll or webhook is reported and retried at the next interval from the same checkpoint,
+// only --once fails on it.
+async fn handle_watch_inventories(args: WatchInventoriesArgs, ctx: &DiciContext) -> Result<()> {
+    let mut checkpoint = match &args.checkpoint {
+        Some(path) => WatchCheckpoint::load(path)?,
+        None => None,
+    }
+    .unwrap_or_else(|| WatchCheckpoint::new(args.since.unwrap_or_else(Utc::now)));
+    let webhook = args
+        .webhook
+        .map(|url| Webhook::new(url, args.webhook_retries))
+        .transpose()?;
+    let interval = args
+        .interval
+        .to_std()
+        .context("Interval must not be negative")?;
+    if interval < MIN_INTERVAL {
+        bail!("Interval must be at least {}s", MIN_INTERVAL.as_secs());
+    }
+    loop {
+        let step = watch_step(
+            &mut checkpoint,
+            webhook.as_ref(),
+            args.checkpoint.as_ref(),
+            ctx,
+        )
+        .await;
+        if args.once {
+            return step;
+        }
+        if let Err(error) = step {
+            print_error(&error);
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+pub async fn handle_watch(watch_command: WatchCommand, ctx: &DiciContext) -> Result<()> {
+    match watch_command {
+        WatchCommand::Inventories(args) => handle_watch_inventories(args, ctx).await,
+    }
+}
@@ -1,21 +1,75 @@
-use crate::api::http::management::client::ManagementClient;
+use crate::api::context::DiciContext;
+use crate::api::http::management::api::{ManagementApi, default_management_api};
 use crate::api::store::catalog::dici::DiciCatalog;
-use crate::mcp::tools::{DiciCallableTool, DiciToolBox};
+use crate::mcp::tools::{DiciCallableTool, DiciToolBox, DiciWriteToolBox, into_call_err};
+use anyhow::anyhow;
 use async_trait::async_trait;
 use rust_mcp_sdk::schema::{
-    CallToolRequest, CallToolResult, ListToolsRequest, ListToolsResult, RpcError,
-    schema_utils::CallToolError,
+    CallToolRequest, CallToolRequestParams, CallToolResult, ListToolsRequest, ListToolsResult,
+    RpcError, schema_utils::CallToolError,
 };
 use rust_mcp_sdk::{McpServer, mcp_server::ServerHandler};
+use std::sync::Arc;
 use typed_builder::TypedBuilder;
-#[derive(TypedBuilder, Default, Clone)]
+#[derive(TypedBuilder, Clone)]
 pub struct DiciServerHandlerState {
-    pub management_client: ManagementClient,
+    #[builder(default = default_management_api(), setter(into))]
+    pub management_client: Arc<dyn ManagementApi>,
+    #[builder(default)]
     pub dici_catalog: DiciCatalog,
 }
+impl Default for DiciServerHandlerState {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+impl From<DiciContext> for DiciServerHandlerState {
+    fn from(ctx: DiciContext) -> Self {
+        Self::builder()
+            .management_client(ctx.management_client)
+            .dici_catalog(ctx.dici_catalog)
+            .build()
+    }
+}
+impl DiciServerHandlerState {
+    pub fn context(&self) -> DiciContext {
+        DiciContext::builder()
+            .management_client(self.management_client.clone())
+            .dici_catalog(self.dici_catalog.clone())
+            .build()
+    }
+}
 #[derive(TypedBuilder, Default)]
 pub struct DiciServerHandler {
     state: DiciServerHandlerState,
+    #[builder(default)]
+    allow_writes: bool,
+}
+impl DiciServerHandler {
+    fn is_write_tool(name: &str) -> bool {
+        DiciWriteToolBox::tools()
+            .iter()
+            .any(|tool| tool.name == name)
+    }
+    // Write tools are refused unless the server allows writes, even when a client calls them unlisted.
+    pub async fn call_tool(
+        &self,
+        params: CallToolRequestParams,
+    ) -> Result<CallToolResult, CallToolError> {
+        if Self::is_write_tool(&params.name) {
+            if !self.allow_writes {
+                return Err(into_call_err(anyhow!(
+                    "Tool {} changes dici management, start the server with --allow-writes to use it",
+                    params.name
+                )));
+            }
+            let tool_box: DiciWriteToolBox =
+                DiciWriteToolBox::try_from(params).map_err(CallToolError::new)?;
+            return tool_box.call_tool(&self.state).await;
+        }
+        let tool_box: DiciToolBox = DiciToolBox::try_from(params).map_err(CallToolError::new)?;
+        tool_box.call_tool(&self.state).await
+    }
 }
 #[async_trait]
 impl ServerHandler for DiciServerHandler {
@@ -24,10 +78,14 @@ impl ServerHandler for DiciServerHandler {
         _request: ListToolsRequest,
         _runtime: &dyn McpServer,
     ) -> Result<ListToolsResult, RpcError> {
+        let mut tools = DiciToolBox::tools();
+        if self.allow_writes {
+            tools.extend(DiciWriteToolBox::tools());
+        }
         Ok(ListToolsResult {
             meta: None,
             next_cursor: None,
-            tools: DiciToolBox::tools(),
+            tools,
         })
     }
     async fn handle_call_tool_request(
@@ -35,8 +93,6 @@ impl ServerHandler for DiciServerHandler {
         request: CallToolRequest,
         _runtime: &dyn McpServer,
     ) -> Result<CallToolResult, CallToolError> {
-        let tool_box: DiciToolBox =
-            DiciToolBox::try_from(request.params).map_err(CallToolError::new)?;
-        tool_box.call_tool(&self.state).await
+        self.call_tool(request.params).await
     }
 }
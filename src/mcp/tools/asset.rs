@@ -1,6 +1,3 @@
-use crate::api::store::asset::core::CoreAsset;
-use crate::api::store::asset::dici::{CoreArgs, DiciAsset, IcebergArgs};
-use crate::api::store::asset::iceberg::IcebergAsset;
 use crate::api::store::asset::traits::schema_source::SchemaSource;
 use crate::mcp::handler::DiciServerHandlerState;
 use crate::mcp::tools::{DiciCallableTool, into_call_err, json_as_text};
@@ -9,9 +6,10 @@ use rust_mcp_sdk::schema::CallToolResult;
 use rust_mcp_sdk::schema::schema_utils::CallToolError;
 use serde::{Deserialize, Serialize};
 #[mcp_tool(
-    name = "get_schema_of_core_asset",
-    title = "Get the schema of a core asset",
-    description = "Input: { fxf } – The fourByFour identifier of a core asset. \
+    name = "get_schema_of_asset",
+    title = "Get the schema of an asset",
+    description = "Input: { asset } – The asset, written as core:<fxf>, iceberg:<icebergLocation>/<schemaTable>, \
+                   inv:<domain>/<icebergLocation>/<schemaTable> or reg:<path>#<schemaTable>. \
                    Output: The schema of the dataset (list of fields with names and types).",
     idempotent_hint = true,
     destructive_hint = false,
@@ -19,54 +17,18 @@ use serde::{Deserialize, Serialize};
     read_only_hint = true
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
-pub struct AssetGetSchemaByFxf {
-    pub fxf: String,
+pub struct AssetGetSchema {
+    pub asset: String,
 }
-impl DiciCallableTool for AssetGetSchemaByFxf {
+impl DiciCallableTool for AssetGetSchema {
     async fn call_tool(
         &self,
         state: &DiciServerHandlerState,
     ) -> Result<CallToolResult, CallToolError> {
-        let asset: DiciAsset = CoreArgs::builder()
-            .asset(CoreAsset::builder().fxf(&self.fxf).build())
-            .dici_catalog(state.dici_catalog.clone())
-            .management_client(state.management_client.clone())
-            .build()
-            .into();
-        let fields = asset.schema().await.map_err(into_call_err)?;
-        json_as_text(&fields)
-    }
-}
-#[mcp_tool(
-    name = "get_schema_of_iceberg_asset",
-    title = "Get the schema of an iceberg asset",
-    description = "Input: { location, schema_table } – The icebergLocation and schemaTable of an iceberg asset. \
-                   Output: The schema of the dataset (list of fields with names and types).",
-    idempotent_hint = true,
-    destructive_hint = false,
-    open_world_hint = false,
-    read_only_hint = true
-)]
-#[derive(Debug, Deserialize, Serialize, JsonSchema)]
-pub struct AssetGetSchemaByIceberg {
-    pub location: String,
-    pub schema_table: String,
-}
-impl DiciCallableTool for AssetGetSchemaByIceberg {
-    async fn call_tool(
-        &self,
-        state: &DiciServerHandlerState,
-    ) -> Result<CallToolResult, CallToolError> {
-        let asset: DiciAsset = IcebergArgs::builder()
-            .asset(
-                IcebergAsset::builder()
-                    .location(&self.location)
-                    .schema_table(&self.schema_table)
-                    .build(),
-            )
-            .dici_catalog(state.dici_catalog.clone())
-            .build()
-            .into();
+        let asset = state
+            .context()
+            .parse_asset(&self.asset)
+            .map_err(into_call_err)?;
         let fields = asset.schema().await.map_err(into_call_err)?;
         json_as_text(&fields)
     }
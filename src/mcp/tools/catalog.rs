@@ -0,0 +1,58 @@
+use crate::api::store::catalog::browse::CatalogBrowser;
+use crate::mcp::handler::DiciServerHandlerState;
+use crate::mcp::tools::{DiciCallableTool, into_call_err, json_as_text};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+#[mcp_tool(
+    name = "list_catalog_namespaces",
+    title = "List the namespaces in the warehouse catalog",
+    description = "Input: none. \
+                   Output: List of namespace names, each namespace is an icebergLocation.",
+    idempotent_hint = true,
+    destructive_hint = false,
+    open_world_hint = false,
+    read_only_hint = true
+)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct CatalogListNamespaces {}
+impl DiciCallableTool for CatalogListNamespaces {
+    async fn call_tool(
+        &self,
+        state: &DiciServerHandlerState,
+    ) -> Result<CallToolResult, CallToolError> {
+        let namespaces = state
+            .dici_catalog
+            .namespaces()
+            .await
+            .map_err(into_call_err)?;
+        json_as_text(&namespaces)
+    }
+}
+#[mcp_tool(
+    name = "list_catalog_tables",
+    title = "List the tables of a namespace in the warehouse catalog",
+    description = "Input: { namespace } – The namespace (icebergLocation) to list. \
+                   Output: List of tables with icebergLocation, schemaTable and the Inventory objects (fourByFours and domains) mapped to each table.",
+    idempotent_hint = true,
+    destructive_hint = false,
+    open_world_hint = false,
+    read_only_hint = true
+)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct CatalogListTables {
+    pub namespace: String,
+}
+impl DiciCallableTool for CatalogListTables {
+    async fn call_tool(
+        &self,
+        state: &DiciServerHandlerState,
+    ) -> Result<CallToolResult, CallToolError> {
+        let tables = state
+            .dici_catalog
+            .tables_with_inventories(&self.namespace, state.management_client.as_ref())
+            .await
+            .map_err(into_call_err)?;
+        json_as_text(&tables)
+    }
+}
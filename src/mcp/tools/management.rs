@@ -2,6 +2,8 @@ use crate::api::http::management::model::inventory::Inventory;
 use crate::api::http::management::model::registration::Registration;
 use crate::api::http::management::model::sync::IcebergLocationSync;
 use crate::api::http::management::model::version::GitConfig;
+use crate::api::identifier::{Domain, FourByFour, IcebergLocation, RegistrationPath};
+use crate::api::since::parse_since;
 use crate::mcp::handler::DiciServerHandlerState;
 use crate::mcp::tools::{DiciCallableTool, into_call_err, json_as_text};
 use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
@@ -13,7 +15,11 @@ use std::collections::HashMap;
     name = "get_inventory_by_four_by_four",
     title = "Get inventory by a fourByFour",
     description = "Input: { four_by_four } – The fourByFour. \
-                   Output: Inventory object."
+                   Output: Inventory object.",
+    idempotent_hint = true,
+    destructive_hint = false,
+    open_world_hint = false,
+    read_only_hint = true
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct InventoryGetByFxf {
@@ -25,8 +31,9 @@ impl DiciCallableTool for InventoryGetByFxf {
         state: &DiciServerHandlerState,
     ) -> Result<CallToolResult, CallToolError> {
         let client = &state.management_client;
+        let four_by_four: FourByFour = self.four_by_four.parse().map_err(into_call_err)?;
         let inv: Inventory = client
-            .fetch_inventory_by_fxf(self.four_by_four.clone())
+            .fetch_inventory_by_fxf(&four_by_four)
             .await
             .map_err(into_call_err)?;
         json_as_text(&inv)
@@ -36,7 +43,11 @@ impl DiciCallableTool for InventoryGetByFxf {
     name = "list_inventory_by_iceberg_location",
     title = "List inventories by icebergLocation",
     description = "Input: { iceberg_location } – The icebergLocation. \
-                   Output: List of Inventory objects."
+                   Output: List of Inventory objects.",
+    idempotent_hint = true,
+    destructive_hint = false,
+    open_world_hint = false,
+    read_only_hint = true
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct InventoryListByIcebergLocation {
@@ -48,8 +59,10 @@ impl DiciCallableTool for InventoryListByIcebergLocation {
         state: &DiciServerHandlerState,
     ) -> Result<CallToolResult, CallToolError> {
         let client = &state.management_client;
+        let iceberg_location: IcebergLocation =
+            self.iceberg_location.parse().map_err(into_call_err)?;
         let list: Vec<Inventory> = client
-            .fetch_inventories_by_iceberg_location(self.iceberg_location.clone())
+            .fetch_inventories_by_iceberg_location(&iceberg_location)
             .await
             .map_err(into_call_err)?;
         json_as_text(&list)
@@ -59,7 +72,11 @@ impl DiciCallableTool for InventoryListByIcebergLocation {
     name = "list_inventory_by_domain",
     title = "List inventories by domain",
     description = "Input: { domain } – The domain. \
-                   Output: List of Inventory objects."
+                   Output: List of Inventory objects.",
+    idempotent_hint = true,
+    destructive_hint = false,
+    open_world_hint = false,
+    read_only_hint = true
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct InventoryListByDomain {
@@ -71,8 +88,9 @@ impl DiciCallableTool for InventoryListByDomain {
         state: &DiciServerHandlerState,
     ) -> Result<CallToolResult, CallToolError> {
         let client = &state.management_client;
+        let domain: Domain = self.domain.parse().map_err(into_call_err)?;
         let list: Vec<Inventory> = client
-            .fetch_inventories_by_domain(self.domain.clone())
+            .fetch_inventories_by_domain(&domain)
             .await
             .map_err(into_call_err)?;
         json_as_text(&list)
@@ -81,8 +99,12 @@ impl DiciCallableTool for InventoryListByDomain {
 #[mcp_tool(
     name = "list_inventory_updated_since",
     title = "List inventories updates since a datetime",
-    description = "Input: { since } – ISO-8601 datetime. \
-                   Output: List of Inventory objects."
+    description = "Input: { since } – ISO-8601 datetime, or a duration back from now like 2h or 1d. \
+                   Output: List of Inventory objects.",
+    idempotent_hint = true,
+    destructive_hint = false,
+    open_world_hint = false,
+    read_only_hint = true
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct InventoryListUpdatedSince {
@@ -93,12 +115,8 @@ impl DiciCallableTool for InventoryListUpdatedSince {
         &self,
         state: &DiciServerHandlerState,
     ) -> Result<CallToolResult, CallToolError> {
-        use chrono::{DateTime, Utc};
         let client = &state.management_client;
-        let since_dt: DateTime<Utc> = self
-            .since
-            .parse()
-            .map_err(|e| into_call_err(format!("Invalid datetime format for 'since': {}", e)))?;
+        let since_dt = parse_since(&self.since).map_err(into_call_err)?;
         let list: Vec<Inventory> = client
             .fetch_inventories_updated_since(since_dt)
             .await
@@ -110,7 +128,11 @@ impl DiciCallableTool for InventoryListUpdatedSince {
     name = "list_inventory_by_iceberg_location_and_table",
     title = "List inventories by icebergLocation and schemaTable",
     description = "Input: { iceberg_location, schema_table } – The icebergLocation and schemaTable. \
-                   Output: List of Inventory objects."
+                   Output: List of Inventory objects.",
+    idempotent_hint = true,
+    destructive_hint = false,
+    open_world_hint = false,
+    read_only_hint = true
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct InventoryListByIcebergLocationAndTable {
@@ -123,11 +145,10 @@ impl DiciCallableTool for InventoryListByIcebergLocationAndTable {
         state: &DiciServerHandlerState,
     ) -> Result<CallToolResult, CallToolError> {
         let client = &state.management_client;
+        let iceberg_location: IcebergLocation =
+            self.iceberg_location.parse().map_err(into_call_err)?;
         let list: Vec<Inventory> = client
-            .fetch_inventories_by_iceberg_location_and_table(
-                self.iceberg_location.clone(),
-                self.schema_table.clone(),
-            )
+            .fetch_inventories_by_iceberg_location_and_table(&iceberg_location, &self.schema_table)
             .await
             .map_err(into_call_err)?;
         json_as_text(&list)
@@ -137,7 +158,11 @@ impl DiciCallableTool for InventoryListByIcebergLocationAndTable {
     name = "get_inventory_by_id",
     title = "Get inventory by domain, icebergLocation, and schemaTable",
     description = "Input: { domain, iceberg_location, schema_table } – The domain, icebergLocation, and schemaTable. \
-                   Output: Inventory object."
+                   Output: Inventory object.",
+    idempotent_hint = true,
+    destructive_hint = false,
+    open_world_hint = false,
+    read_only_hint = true
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct InventoryGetById {
@@ -151,12 +176,11 @@ impl DiciCallableTool for InventoryGetById {
         state: &DiciServerHandlerState,
     ) -> Result<CallToolResult, CallToolError> {
         let client = &state.management_client;
+        let domain: Domain = self.domain.parse().map_err(into_call_err)?;
+        let iceberg_location: IcebergLocation =
+            self.iceberg_location.parse().map_err(into_call_err)?;
         let inv: Inventory = client
-            .fetch_inventory_by_id(
-                self.domain.clone(),
-                self.iceberg_location.clone(),
-                self.schema_table.clone(),
-            )
+            .fetch_inventory_by_id(&domain, &iceberg_location, &self.schema_table)
             .await
             .map_err(into_call_err)?;
         json_as_text(&inv)
@@ -166,7 +190,11 @@ impl DiciCallableTool for InventoryGetById {
     name = "list_registrations_by_path",
     title = "List registrations by path",
     description = "Input: { path } – The registration path. \
-                   Output: List of Registration objects."
+                   Output: List of Registration objects.",
+    idempotent_hint = true,
+    destructive_hint = false,
+    open_world_hint = false,
+    read_only_hint = true
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct RegistrationListByPath {
@@ -178,8 +206,9 @@ impl DiciCallableTool for RegistrationListByPath {
         state: &DiciServerHandlerState,
     ) -> Result<CallToolResult, CallToolError> {
         let client = &state.management_client;
+        let path: RegistrationPath = self.path.parse().map_err(into_call_err)?;
         let regs: Vec<Registration> = client
-            .fetch_registrations_by_path(self.path.clone())
+            .fetch_registrations_by_path(&path)
             .await
             .map_err(into_call_err)?;
         json_as_text(&regs)
@@ -189,7 +218,11 @@ impl DiciCallableTool for RegistrationListByPath {
     name = "get_registration_by_iceberg_location",
     title = "Get a registration by icebergLocation",
     description = "Input: { iceberg_location } – The icebergLocation. \
-                   Output: Registration object."
+                   Output: Registration object.",
+    idempotent_hint = true,
+    destructive_hint = false,
+    open_world_hint = false,
+    read_only_hint = true
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct RegistrationGetByIcebergLocation {
@@ -201,8 +234,10 @@ impl DiciCallableTool for RegistrationGetByIcebergLocation {
         state: &DiciServerHandlerState,
     ) -> Result<CallToolResult, CallToolError> {
         let client = &state.management_client;
+        let iceberg_location: IcebergLocation =
+            self.iceberg_location.parse().map_err(into_call_err)?;
         let reg: Registration = client
-            .fetch_registration_by_iceberg_location(self.iceberg_location.clone())
+            .fetch_registration_by_iceberg_location(&iceberg_location)
             .await
             .map_err(into_call_err)?;
         json_as_text(&reg)
@@ -212,7 +247,11 @@ impl DiciCallableTool for RegistrationGetByIcebergLocation {
     name = "list_registration_by_path_and_metadata",
     title = "List registrations by path and metadata",
     description = "Input: { path, metadata } – The registration path and metadata key-value filters. \
-                   Output: List of Registration objects."
+                   Output: List of Registration objects.",
+    idempotent_hint = true,
+    destructive_hint = false,
+    open_world_hint = false,
+    read_only_hint = true
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct RegistrationQueryByPathAndMetadata {
@@ -226,8 +265,9 @@ impl DiciCallableTool for RegistrationQueryByPathAndMetadata {
         state: &DiciServerHandlerState,
     ) -> Result<CallToolResult, CallToolError> {
         let client = &state.management_client;
+        let path: RegistrationPath = self.path.parse().map_err(into_call_err)?;
         let regs: Vec<Registration> = client
-            .fetch_registrations_by_path_and_metadata(self.path.clone(), self.metadata.clone())
+            .fetch_registrations_by_path_and_metadata(&path, &self.metadata)
             .await
             .map_err(into_call_err)?;
         json_as_text(&regs)
@@ -237,7 +277,11 @@ impl DiciCallableTool for RegistrationQueryByPathAndMetadata {
     name = "get_dici_management_build_information",
     title = "Get the build information of dici management",
     description = "Input: none. \
-                   Output: GitConfig object."
+                   Output: GitConfig object.",
+    idempotent_hint = true,
+    destructive_hint = false,
+    open_world_hint = false,
+    read_only_hint = true
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct GetDiciManagementBuildInformation {}
@@ -255,7 +299,11 @@ impl DiciCallableTool for GetDiciManagementBuildInformation {
     name = "sync_table",
     title = "Sync an iceberg table",
     description = "Input: { iceberg_location, schema_table } – The icebergLocation and schemaTable. \
-                   Output: List of Inventory objects."
+                   Output: List of Inventory objects.",
+    idempotent_hint = true,
+    destructive_hint = false,
+    open_world_hint = false,
+    read_only_hint = false
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct SyncTable {
@@ -268,8 +316,10 @@ impl DiciCallableTool for SyncTable {
         state: &DiciServerHandlerState,
     ) -> Result<CallToolResult, CallToolError> {
         let client = &state.management_client;
+        let iceberg_location: IcebergLocation =
+            self.iceberg_location.parse().map_err(into_call_err)?;
         let list: Vec<Inventory> = client
-            .sync_table(self.iceberg_location.clone(), self.schema_table.clone())
+            .sync_table(&iceberg_location, &self.schema_table)
             .await
             .map_err(into_call_err)?;
         json_as_text(&list)
@@ -279,7 +329,11 @@ impl DiciCallableTool for SyncTable {
     name = "sync_table_domain",
     title = "Sync an iceberg table with explicit domain",
     description = "Input: { domain, iceberg_location, schema_table } – The domain, icebergLocation, and schemaTable. \
-                   Output: Inventory object."
+                   Output: Inventory object.",
+    idempotent_hint = true,
+    destructive_hint = false,
+    open_world_hint = false,
+    read_only_hint = false
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct SyncTableDomain {
@@ -293,12 +347,11 @@ impl DiciCallableTool for SyncTableDomain {
         state: &DiciServerHandlerState,
     ) -> Result<CallToolResult, CallToolError> {
         let client = &state.management_client;
+        let domain: Domain = self.domain.parse().map_err(into_call_err)?;
+        let iceberg_location: IcebergLocation =
+            self.iceberg_location.parse().map_err(into_call_err)?;
         let inv: Inventory = client
-            .sync_table_domain(
-                self.domain.clone(),
-                self.iceberg_location.clone(),
-                self.schema_table.clone(),
-            )
+            .sync_table_domain(&domain, &iceberg_location, &self.schema_table)
             .await
             .map_err(into_call_err)?;
         json_as_text(&inv)
@@ -308,7 +361,11 @@ impl DiciCallableTool for SyncTableDomain {
     name = "sync_iceberg_location",
     title = "Sync an entire iceberg location",
     description = "Input: { iceberg_location } – The icebergLocation. \
-                   Output: IcebergLocationSync object with successes and failures."
+                   Output: IcebergLocationSync object with successes and failures.",
+    idempotent_hint = true,
+    destructive_hint = false,
+    open_world_hint = false,
+    read_only_hint = false
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct SyncIcebergLocation {
@@ -320,8 +377,10 @@ impl DiciCallableTool for SyncIcebergLocation {
         state: &DiciServerHandlerState,
     ) -> Result<CallToolResult, CallToolError> {
         let client = &state.management_client;
+        let iceberg_location: IcebergLocation =
+            self.iceberg_location.parse().map_err(into_call_err)?;
         let sync_result: IcebergLocationSync = client
-            .sync_iceberg_location(self.iceberg_location.clone())
+            .sync_iceberg_location(&iceberg_location)
             .await
             .map_err(into_call_err)?;
         json_as_text(&sync_result)
@@ -1,13 +1,16 @@
+use crate::api::error::{ErrorKind, ErrorReport};
 use crate::mcp::handler::DiciServerHandlerState;
-use crate::mcp::tools::asset::{AssetGetSchemaByFxf, AssetGetSchemaByIceberg};
+use crate::mcp::tools::asset::AssetGetSchema;
+use crate::mcp::tools::catalog::{CatalogListNamespaces, CatalogListTables};
 use crate::mcp::tools::datetime::GetDateTimeTool;
 use crate::mcp::tools::management::{
-    InventoryGetByFxf, InventoryGetById, InventoryListByDomain, InventoryListByIcebergLocation,
-    InventoryListByIcebergLocationAndTable, InventoryListUpdatedSince,
-    RegistrationGetByIcebergLocation, RegistrationListByPath, RegistrationQueryByPathAndMetadata,
-    GetDiciManagementBuildInformation,
+    GetDiciManagementBuildInformation, InventoryGetByFxf, InventoryGetById, InventoryListByDomain,
+    InventoryListByIcebergLocation, InventoryListByIcebergLocationAndTable,
+    InventoryListUpdatedSince, RegistrationGetByIcebergLocation, RegistrationListByPath,
+    RegistrationQueryByPathAndMetadata, SyncIcebergLocation, SyncTable, SyncTableDomain,
 };
-use crate::mcp::tools::sql::{AssetExecuteSqlByFxf, AssetExecuteSqlByIceberg};
+use crate::mcp::tools::resolve::ResolveIdentifier;
+use crate::mcp::tools::sql::{AssetExecuteSql, AssetExecuteSqlMulti, ExecuteSql};
 use arrow::record_batch::RecordBatch;
 use arrow_json::ArrayWriter;
 use rust_mcp_sdk::schema::schema_utils::{CallToolError, SdkError};
@@ -16,11 +19,34 @@ use rust_mcp_sdk::tool_box;
 use serde::Serialize;
 use serde_json::Value;
 pub mod asset;
+pub mod catalog;
 pub mod datetime;
 pub mod management;
+pub mod resolve;
 pub mod sql;
-pub fn into_call_err<E: std::fmt::Display>(e: E) -> CallToolError {
-    CallToolError::new(SdkError::internal_error().with_message(&e.to_string()))
+// Codes from -32000 to -32099 are left to the server by JSON-RPC, -32001 and -32002 are the ones the
+// mcp spec uses for timeouts and missing resources.
+fn rpc_code(kind: ErrorKind) -> i64 {
+    match kind {
+        ErrorKind::BadIdentifier => -32602,
+        ErrorKind::Timeout => -32001,
+        ErrorKind::NotFound => -32002,
+        ErrorKind::Unauthorized => -32010,
+        ErrorKind::ManagementStatus | ErrorKind::Unavailable => -32011,
+        ErrorKind::Catalog => -32012,
+        ErrorKind::Query => -32013,
+        ErrorKind::Config => -32014,
+        ErrorKind::Other => -32603,
+    }
+}
+// The error report goes in the error data so clients can act on its kind.
+pub fn into_call_err<E: Into<anyhow::Error>>(e: E) -> CallToolError {
+    let report = ErrorReport::from(&e.into());
+    let mut error = SdkError::internal_error()
+        .with_message(&report.message)
+        .with_data(serde_json::to_value(&report).ok());
+    error.code = rpc_code(report.kind);
+    CallToolError::new(error)
 }
 pub fn json_as_text<T: Serialize>(value: &T) -> Result<CallToolResult, CallToolError> {
     let pretty_json = serde_json::to_string_pretty(value).map_err(into_call_err)?;
@@ -86,9 +112,17 @@ tool_box_with_dispatch!(
         RegistrationListByPath,
         RegistrationQueryByPathAndMetadata,
         GetDiciManagementBuildInformation,
-        AssetGetSchemaByFxf,
-        AssetGetSchemaByIceberg,
-        AssetExecuteSqlByIceberg,
-        AssetExecuteSqlByFxf
+        AssetGetSchema,
+        AssetExecuteSql,
+        AssetExecuteSqlMulti,
+        ExecuteSql,
+        CatalogListNamespaces,
+        CatalogListTables,
+        ResolveIdentifier
     ]
 );
+// Tools that change dici management, only listed and callable when the server allows writes.
+tool_box_with_dispatch!(
+    DiciWriteToolBox,
+    [SyncTable, SyncTableDomain, SyncIcebergLocation]
+);
@@ -0,0 +1,36 @@
+use crate::api::resolve::Identifier;
+use crate::mcp::handler::DiciServerHandlerState;
+use crate::mcp::tools::{DiciCallableTool, into_call_err, json_as_text};
+use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
+use rust_mcp_sdk::schema::{CallToolResult, schema_utils::CallToolError};
+use serde::{Deserialize, Serialize};
+#[mcp_tool(
+    name = "resolve_identifier",
+    title = "Resolve any identifier to everything connected to it",
+    description = "Input: { identifier } – A fourByFour, icebergLocation, <icebergLocation>/<schemaTable>, domain, \
+                   <domain>/<icebergLocation>/<schemaTable>, registration path or asset such as core:<fxf>, the type is detected. \
+                   Output: The detected identifier kind, the Inventory objects it names, the Registration objects (with metadata) of their icebergLocations, \
+                   sibling Inventory objects sharing those icebergLocations across domains, and each table with whether it exists in the catalog and its current snapshot.",
+    idempotent_hint = true,
+    destructive_hint = false,
+    open_world_hint = false,
+    read_only_hint = true
+)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ResolveIdentifier {
+    pub identifier: String,
+}
+impl DiciCallableTool for ResolveIdentifier {
+    async fn call_tool(
+        &self,
+        state: &DiciServerHandlerState,
+    ) -> Result<CallToolResult, CallToolError> {
+        let identifier: Identifier = self.identifier.parse().map_err(into_call_err)?;
+        let graph = state
+            .context()
+            .resolve(identifier)
+            .await
+            .map_err(into_call_err)?;
+        json_as_text(&graph)
+    }
+}
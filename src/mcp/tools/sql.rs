@@ -1,104 +1,147 @@
-use crate::api::store::asset::core::CoreAsset;
-use crate::api::store::asset::dici::{CoreArgs, DiciAsset, IcebergArgs};
-use crate::api::store::asset::iceberg::IcebergAsset;
-use crate::api::store::asset::traits::manually_sqlable::ManuallySqlAble;
+use crate::api::store::asset::version::TableVersion;
+use crate::api::store::session::{DiciSession, read_only_sql_options};
 use crate::mcp::handler::DiciServerHandlerState;
-use crate::mcp::tools::{DiciCallableTool, into_call_err, json_as_text};
-use arrow_json::ArrayWriter;
-use datafusion::prelude::SQLOptions;
+use crate::mcp::tools::{
+    DiciCallableTool, into_call_err, json_as_text, record_batches_to_json_values,
+};
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
 use datafusion::sql::TableReference;
 use rust_mcp_sdk::macros::{JsonSchema, mcp_tool};
 use rust_mcp_sdk::schema::CallToolResult;
 use rust_mcp_sdk::schema::schema_utils::CallToolError;
-use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 #[mcp_tool(
-    name = "execute_sql_against_core_asset",
-    title = "Execute SQL against a core asset",
-    description = "Input: { fxf, sql } – fourByFour of the core asset and a SQL query string. \
+    name = "execute_sql_against_asset",
+    title = "Execute SQL against an asset",
+    description = "Input: { asset, sql, snapshot_id?, as_of?, reference? } – an asset and a SQL query string. \
+                   The asset is written as core:<fxf>, iceberg:<icebergLocation>/<schemaTable>, \
+                   inv:<domain>/<icebergLocation>/<schemaTable> or reg:<path>#<schemaTable>. \
                    The SQL must reference the dataset as the table name \"this\". \
+                   Optionally one of snapshot_id, as_of (ISO-8601 datetime) or reference (branch or tag) reads the table as it was then. \
                    Output: Query results as JSON values."
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
-pub struct AssetExecuteSqlByFxf {
-    pub fxf: String,
+pub struct AssetExecuteSql {
+    pub asset: String,
     pub sql: String,
+    pub snapshot_id: Option<i64>,
+    pub as_of: Option<String>,
+    pub reference: Option<String>,
 }
-impl DiciCallableTool for AssetExecuteSqlByFxf {
+impl DiciCallableTool for AssetExecuteSql {
     async fn call_tool(
         &self,
         state: &DiciServerHandlerState,
     ) -> Result<CallToolResult, CallToolError> {
-        let asset: DiciAsset = CoreArgs::builder()
-            .asset(CoreAsset::builder().fxf(&self.fxf).build())
-            .dici_catalog(state.dici_catalog.clone())
-            .management_client(state.management_client.clone())
-            .build()
-            .into();
-        let x: Vec<Value> = run_sql_and_return_json(&asset, &self.sql)
+        let asset = state
+            .context()
+            .parse_asset(&self.asset)
+            .map_err(into_call_err)?
+            .with_version(table_version(
+                self.snapshot_id,
+                &self.as_of,
+                &self.reference,
+            )?);
+        let session = state.context().session().map_err(into_call_err)?;
+        session
+            .register(TableReference::bare("this"), &asset)
+            .await
+            .map_err(into_call_err)?;
+        let x: Vec<Value> = run_session_sql(&session, &self.sql)
             .await
             .map_err(into_call_err)?;
         json_as_text(&x)
     }
 }
 #[mcp_tool(
-    name = "execute_sql_against_iceberg_asset",
-    title = "Execute SQL against an iceberg asset",
-    description = "Input: { iceberg_location, schema_table, sql } – icebergLocation and schemaTable of an iceberg asset, and a SQL query string. \
-                   The SQL must reference the dataset as the table name \"this\". \
+    name = "execute_sql_against_assets",
+    title = "Execute SQL against several assets",
+    description = "Input: { assets, sql, snapshot_id?, as_of?, reference? } – a list of aliased assets and a SQL query string. \
+                   Each asset is written as alias=core:<fxf>, alias=iceberg:<icebergLocation>/<schemaTable>, \
+                   alias=inv:<domain>/<icebergLocation>/<schemaTable> or alias=reg:<path>#<schemaTable>. \
+                   The SQL must reference each dataset by its alias, so assets can be joined. \
+                   Optionally one of snapshot_id, as_of (ISO-8601 datetime) or reference (branch or tag) reads every asset as it was then. \
+                   Output: Query results as JSON values."
+)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct AssetExecuteSqlMulti {
+    pub assets: Vec<String>,
+    pub sql: String,
+    pub snapshot_id: Option<i64>,
+    pub as_of: Option<String>,
+    pub reference: Option<String>,
+}
+impl DiciCallableTool for AssetExecuteSqlMulti {
+    async fn call_tool(
+        &self,
+        state: &DiciServerHandlerState,
+    ) -> Result<CallToolResult, CallToolError> {
+        let ctx = state.context();
+        let version = table_version(self.snapshot_id, &self.as_of, &self.reference)?;
+        let assets = ctx
+            .parse_aliased_assets(&self.assets, &version)
+            .map_err(into_call_err)?;
+        let session = ctx.session().map_err(into_call_err)?;
+        session
+            .register_all(assets.iter().map(|(alias, asset)| (alias.as_str(), asset)))
+            .await
+            .map_err(into_call_err)?;
+        let x: Vec<Value> = run_session_sql(&session, &self.sql)
+            .await
+            .map_err(into_call_err)?;
+        json_as_text(&x)
+    }
+}
+#[mcp_tool(
+    name = "execute_sql",
+    title = "Execute SQL against any asset",
+    description = "Input: { sql, snapshot_id?, as_of?, reference? } – a SQL query string. \
+                   Core assets are referenced as core.\"<fxf>\" and iceberg assets as iceberg.\"<icebergLocation>\".\"<schemaTable>\", \
+                   they are resolved when the query runs and can be joined freely. \
+                   Optionally one of snapshot_id, as_of (ISO-8601 datetime) or reference (branch or tag) reads every table as it was then. \
                    Output: Query results as JSON values."
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
-pub struct AssetExecuteSqlByIceberg {
-    pub iceberg_location: String,
-    pub schema_table: String,
+pub struct ExecuteSql {
     pub sql: String,
+    pub snapshot_id: Option<i64>,
+    pub as_of: Option<String>,
+    pub reference: Option<String>,
 }
-impl DiciCallableTool for AssetExecuteSqlByIceberg {
+impl DiciCallableTool for ExecuteSql {
     async fn call_tool(
         &self,
         state: &DiciServerHandlerState,
     ) -> Result<CallToolResult, CallToolError> {
-        let asset: DiciAsset = IcebergArgs::builder()
-            .asset(
-                IcebergAsset::builder()
-                    .location(&self.iceberg_location)
-                    .schema_table(&self.schema_table)
-                    .build(),
-            )
-            .dici_catalog(state.dici_catalog.clone())
-            .build()
-            .into();
-        let x: Vec<Value> = run_sql_and_return_json(&asset, &self.sql)
+        let version = table_version(self.snapshot_id, &self.as_of, &self.reference)?;
+        let session = state
+            .context()
+            .session_at(&version)
+            .map_err(into_call_err)?;
+        let x: Vec<Value> = run_session_sql(&session, &self.sql)
             .await
             .map_err(into_call_err)?;
         json_as_text(&x)
     }
 }
-async fn run_sql_and_return_json<T>(asset: &DiciAsset, sql: &str) -> anyhow::Result<T>
-where
-    T: DeserializeOwned,
-{
-    let options = SQLOptions::new()
-        .with_allow_ddl(false)
-        .with_allow_dml(false)
-        .with_allow_statements(false);
-    let table_reference = TableReference::Bare {
-        table: "this".into(),
-    };
-    let dataframe = asset
-        .sql_with_table_reference_and_options(sql, table_reference, options)
+fn table_version(
+    snapshot_id: Option<i64>,
+    as_of: &Option<String>,
+    reference: &Option<String>,
+) -> Result<TableVersion, CallToolError> {
+    let as_of = as_of
+        .as_deref()
+        .map(str::parse::<DateTime<Utc>>)
+        .transpose()
+        .map_err(|e| into_call_err(anyhow!("Invalid datetime format for 'as_of': {}", e)))?;
+    TableVersion::from_options(snapshot_id, as_of, reference.clone()).map_err(into_call_err)
+}
+async fn run_session_sql(session: &DiciSession, sql: &str) -> anyhow::Result<Vec<Value>> {
+    let dataframe = session
+        .sql_with_options(sql, read_only_sql_options())
         .await?;
     let results = dataframe.collect().await?;
-    let mut buf = Vec::new();
-    {
-        let mut writer = ArrayWriter::new(&mut buf);
-        for batch in &results {
-            writer.write(batch)?;
-        }
-        writer.finish()?;
-    }
-    let json_values: T = serde_json::from_slice(&buf)?;
-    Ok(json_values)
+    record_batches_to_json_values(&results)
 }
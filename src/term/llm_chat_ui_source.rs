@@ -71,6 +71,8 @@ Registration
 GitConfig
 - Direct fields: commit, branch, build number, build time, author
 - Operations: inspect build and deployment info
+Any identifier
+- Operations: resolve_identifier detects the type and returns the whole connected graph at once (Inventories, Registrations, sibling Inventories, tables with their current snapshot)
 "#;
 #[derive(Clone)]
 struct PendingReq {
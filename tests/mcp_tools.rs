@@ -0,0 +1,158 @@
+use diceberg::api::http::management::memory::InMemoryManagement;
+use diceberg::api::http::management::snapshot::SnapshotManagement;
+use diceberg::mcp::handler::{DiciServerHandler, DiciServerHandlerState};
+use rust_mcp_sdk::schema::CallToolRequestParams;
+use rust_mcp_sdk::schema::schema_utils::{CallToolError, SdkError};
+use serde_json::{Value, json};
+use std::path::Path;
+// Dispatches tools the way the server does, against the management state held in tests/fixtures.
+fn handler(allow_writes: bool) -> (DiciServerHandler, InMemoryManagement) {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/snapshot.json");
+    let management = InMemoryManagement::from(SnapshotManagement::read(&fixtures).unwrap());
+    let state = DiciServerHandlerState::builder()
+        .management_client(management.clone())
+        .build();
+    let handler = DiciServerHandler::builder()
+        .state(state)
+        .allow_writes(allow_writes)
+        .build();
+    (handler, management)
+}
+async fn call(handler: &DiciServerHandler, name: &str, arguments: Value) -> Result<Value, i64> {
+    let params = CallToolRequestParams {
+        name: name.to_string(),
+        arguments: arguments.as_object().cloned(),
+    };
+    match handler.call_tool(params).await {
+        Ok(result) => {
+            let text = &result.content[0].as_text_content().unwrap().text;
+            Ok(serde_json::from_str(text).unwrap())
+        }
+        Err(error) => Err(code(&error)),
+    }
+}
+fn code(error: &CallToolError) -> i64 {
+    error.0.downcast_ref::<SdkError>().unwrap().code
+}
+const PERMITS: &str = "_0123456789abcdef0123456789abcdef";
+#[tokio::test]
+async fn lookup_tools_answer_from_management() {
+    let (handler, _) = handler(false);
+    let inventory = call(
+        &handler,
+        "get_inventory_by_four_by_four",
+        json!({"four_by_four": "abcd-1234"}),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        inventory["id"]["schemaTable"]["schemaTable"],
+        "incode.permits"
+    );
+    let listed = [
+        (
+            "list_inventory_by_iceberg_location",
+            json!({"iceberg_location": PERMITS}),
+            2,
+        ),
+        (
+            "list_inventory_by_domain",
+            json!({"domain": "open.example.org"}),
+            1,
+        ),
+        (
+            "list_inventory_updated_since",
+            json!({"since": "2024-01-15T00:00:00Z"}),
+            2,
+        ),
+        (
+            "list_inventory_by_iceberg_location_and_table",
+            json!({"iceberg_location": PERMITS, "schema_table": "incode.permits"}),
+            1,
+        ),
+        (
+            "list_registrations_by_path",
+            json!({"path": "erp_pro_10"}),
+            2,
+        ),
+        (
+            "list_registration_by_path_and_metadata",
+            json!({"path": "erp_pro_10", "metadata": {"owner": "finance"}}),
+            1,
+        ),
+    ];
+    for (name, arguments, count) in listed {
+        let answer = call(&handler, name, arguments).await.unwrap();
+        assert_eq!(answer.as_array().unwrap().len(), count, "{}", name);
+    }
+    let inventory = call(
+        &handler,
+        "get_inventory_by_id",
+        json!({"domain": "data.example.gov", "iceberg_location": PERMITS, "schema_table": "incode.inspections"}),
+    )
+    .await
+    .unwrap();
+    assert_eq!(inventory["fourByFour"]["fourByFour"], "efgh-5678");
+    let registration = call(
+        &handler,
+        "get_registration_by_iceberg_location",
+        json!({"iceberg_location": PERMITS}),
+    )
+    .await
+    .unwrap();
+    assert_eq!(registration["id"]["path"], "erp_pro_10/incode");
+    let version = call(&handler, "get_dici_management_build_information", json!({}))
+        .await
+        .unwrap();
+    assert_eq!(version["build"]["version"], "1.4.0");
+}
+#[tokio::test]
+async fn errors_carry_their_rpc_code() {
+    let (handler, _) = handler(false);
+    let bad = json!({"four_by_four": "not a fxf"});
+    assert_eq!(
+        call(&handler, "get_inventory_by_four_by_four", bad).await,
+        Err(-32602)
+    );
+    let missing = json!({"four_by_four": "zzzz-0000"});
+    assert_eq!(
+        call(&handler, "get_inventory_by_four_by_four", missing).await,
+        Err(-32002)
+    );
+    let twice = json!({"assets": ["a=core:abcd-1234", "a=core:efgh-5678"], "sql": "select 1"});
+    assert_eq!(
+        call(&handler, "execute_sql_against_assets", twice).await,
+        Err(-32602)
+    );
+}
+#[tokio::test]
+async fn sync_tools_need_allow_writes() {
+    let sync = json!({"iceberg_location": PERMITS, "schema_table": "incode.permits"});
+    let (refusing, management) = handler(false);
+    let before = management.snapshot();
+    let refused = call(&refusing, "sync_table", sync.clone()).await;
+    assert!(refused.is_err());
+    let after = management.snapshot();
+    assert_eq!(
+        serde_json::to_value(&before.inventories).unwrap(),
+        serde_json::to_value(&after.inventories).unwrap()
+    );
+    let (allowing, management) = handler(true);
+    let synced = call(&allowing, "sync_table", sync).await.unwrap();
+    assert_eq!(synced.as_array().unwrap().len(), 1);
+    let permits = management
+        .snapshot()
+        .inventories
+        .into_iter()
+        .find(|inventory| inventory.id.schema_table.schema_table == "incode.permits")
+        .unwrap();
+    assert!(permits.updated_at > before.inventories[0].updated_at);
+    let synced = call(
+        &allowing,
+        "sync_iceberg_location",
+        json!({"iceberg_location": PERMITS}),
+    )
+    .await
+    .unwrap();
+    assert_eq!(synced["successes"].as_array().unwrap().len(), 2);
+}
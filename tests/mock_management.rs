@@ -0,0 +1,162 @@
+use chrono::{DateTime, Utc};
+use diceberg::api::error::ErrorKind;
+use diceberg::api::http::management::api::ManagementApi;
+use diceberg::api::http::management::client::ManagementClient;
+use diceberg::api::http::management::config::{ManagementConfig, ManagementOptions};
+use diceberg::api::http::management::memory::InMemoryManagement;
+use diceberg::api::http::management::mock::{read_fixtures, router};
+use diceberg::api::http::management::model::inventory::Inventory;
+use diceberg::api::identifier::{Domain, IcebergLocation, RegistrationPath};
+use std::collections::HashMap;
+use std::path::Path;
+use tokio::net::TcpListener;
+// Round trips every endpoint through ManagementClient and the mock, answered from tests/fixtures.
+const PERMITS: &str = "_0123456789abcdef0123456789abcdef";
+const BUDGET: &str = "_fedcba9876543210fedcba9876543210";
+async fn serve() -> (ManagementClient, InMemoryManagement) {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/snapshot.json");
+    let management = InMemoryManagement::from(read_fixtures(&fixtures).unwrap());
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let app = router(management.clone());
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    let config = ManagementConfig::builder()
+        .address(address)
+        .options(ManagementOptions {
+            retries: Some(0),
+            ..ManagementOptions::default()
+        })
+        .build();
+    (
+        ManagementClient::builder().config(config).build(),
+        management,
+    )
+}
+fn location(location: &str) -> IcebergLocation {
+    location.parse().unwrap()
+}
+fn tables(inventories: &[Inventory]) -> Vec<&str> {
+    let mut tables: Vec<&str> = inventories
+        .iter()
+        .map(|inventory| inventory.id.schema_table.schema_table.as_str())
+        .collect();
+    tables.sort();
+    tables
+}
+#[tokio::test]
+async fn inventories_round_trip() {
+    let (client, _) = serve().await;
+    let all = client.fetch_inventories().await.unwrap();
+    assert_eq!(
+        tables(&all),
+        ["finance.budget", "incode.inspections", "incode.permits"]
+    );
+    let by_fxf = client
+        .fetch_inventory_by_fxf(&"efgh-5678".parse().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(by_fxf.id.schema_table.schema_table, "incode.inspections");
+    let by_location = client
+        .fetch_inventories_by_iceberg_location(&location(PERMITS))
+        .await
+        .unwrap();
+    assert_eq!(
+        tables(&by_location),
+        ["incode.inspections", "incode.permits"]
+    );
+    let by_table = client
+        .fetch_inventories_by_iceberg_location_and_table(&location(BUDGET), "finance.budget")
+        .await
+        .unwrap();
+    assert_eq!(by_table[0].four_by_four.four_by_four.as_str(), "ijkl-9012");
+    let domain: Domain = "open.example.org".parse().unwrap();
+    let by_domain = client.fetch_inventories_by_domain(&domain).await.unwrap();
+    assert_eq!(tables(&by_domain), ["finance.budget"]);
+    let by_id = client
+        .fetch_inventory_by_id(&domain, &location(BUDGET), "finance.budget")
+        .await
+        .unwrap();
+    assert_eq!(by_id.four_by_four.four_by_four.as_str(), "ijkl-9012");
+    let since: DateTime<Utc> = "2024-01-15T00:00:00Z".parse().unwrap();
+    let updated = client.fetch_inventories_updated_since(since).await.unwrap();
+    assert_eq!(tables(&updated), ["finance.budget", "incode.inspections"]);
+}
+#[tokio::test]
+async fn registrations_and_version_round_trip() {
+    let (client, _) = serve().await;
+    assert_eq!(client.fetch_registrations().await.unwrap().len(), 2);
+    let by_location = client
+        .fetch_registration_by_iceberg_location(&location(BUDGET))
+        .await
+        .unwrap();
+    assert_eq!(by_location.key().as_str(), "erp_pro_10/finance");
+    let path: RegistrationPath = "/erp_pro_10".parse().unwrap();
+    assert_eq!(
+        client
+            .fetch_registrations_by_path(&path)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+    let metadata = HashMap::from([("owner".to_string(), "permits".to_string())]);
+    let by_metadata = client
+        .fetch_registrations_by_path_and_metadata(&path, &metadata)
+        .await
+        .unwrap();
+    assert_eq!(by_metadata.len(), 1);
+    assert_eq!(by_metadata[0].key().as_str(), "erp_pro_10/incode");
+    let version = client.fetch_version().await.unwrap();
+    assert_eq!(version.build.version, "1.4.0");
+}
+#[tokio::test]
+async fn syncs_change_the_served_state() {
+    let (client, management) = serve().await;
+    let before = chrono::Utc::now();
+    let synced = client
+        .sync_table(&location(PERMITS), "incode.permits")
+        .await
+        .unwrap();
+    assert_eq!(tables(&synced), ["incode.permits"]);
+    assert!(synced[0].updated_at >= before);
+    let updated = client
+        .fetch_inventories_updated_since(before)
+        .await
+        .unwrap();
+    assert_eq!(tables(&updated), ["incode.permits"]);
+    let domain: Domain = "open.example.org".parse().unwrap();
+    let synced = client
+        .sync_table_domain(&domain, &location(BUDGET), "finance.budget")
+        .await
+        .unwrap();
+    assert!(synced.updated_at >= before);
+    let synced = client
+        .sync_iceberg_location(&location(PERMITS))
+        .await
+        .unwrap();
+    assert_eq!(
+        tables(&synced.successes),
+        ["incode.inspections", "incode.permits"]
+    );
+    assert!(synced.failures.is_empty());
+    let held = management.snapshot();
+    assert!(
+        held.inventories
+            .iter()
+            .all(|inventory| inventory.updated_at >= before)
+    );
+}
+#[tokio::test]
+async fn errors_keep_their_kind() {
+    let (client, _) = serve().await;
+    let missing = client
+        .fetch_inventory_by_fxf(&"zzzz-0000".parse().unwrap())
+        .await
+        .unwrap_err();
+    assert_eq!(ErrorKind::of(&missing), ErrorKind::NotFound);
+    let missing = client
+        .sync_iceberg_location(&location("_00000000000000000000000000000000"))
+        .await
+        .unwrap_err();
+    assert_eq!(ErrorKind::of(&missing), ErrorKind::NotFound);
+}